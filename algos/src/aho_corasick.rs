@@ -0,0 +1,243 @@
+//! Aho–Corasick multi-pattern search.
+//!
+//! Builds a trie of all patterns, wires failure links with a BFS, and scans the
+//! text once reporting every pattern that ends at each position. Unlike the
+//! single-pattern algorithms this matches a whole dictionary in one pass, so it
+//! is the fastest path when many patterns are searched together.
+
+use crate::{CompileOptions, StringSearch};
+
+/// A single hit: the pattern that matched and the byte offset where it starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hit {
+    pub pattern: usize,
+    pub start: usize,
+}
+
+struct Node {
+    next: [i32; 256],
+    fail: i32,
+    // Patterns ending exactly at this node, plus those reachable via fail links.
+    output: Vec<usize>,
+}
+
+impl Node {
+    fn new() -> Self {
+        Self {
+            next: [-1; 256],
+            fail: 0,
+            output: Vec::new(),
+        }
+    }
+}
+
+/// An automaton matching a fixed set of byte patterns.
+pub struct AhoCorasick {
+    nodes: Vec<Node>,
+    pattern_lens: Vec<usize>,
+}
+
+impl AhoCorasick {
+    /// Build the automaton from the given patterns. Empty patterns are ignored:
+    /// they contribute no trie edges and never appear in `find_all`/`find_first`
+    /// output (an empty pattern has no well-defined match start).
+    pub fn build<P: AsRef<[u8]>>(patterns: &[P]) -> Self {
+        let mut nodes = vec![Node::new()]; // root at index 0
+
+        for (id, pattern) in patterns.iter().enumerate() {
+            let pattern = pattern.as_ref();
+            if pattern.is_empty() {
+                continue;
+            }
+            let mut node = 0usize;
+            for &byte in pattern {
+                let edge = nodes[node].next[byte as usize];
+                node = if edge < 0 {
+                    let new_idx = nodes.len();
+                    nodes.push(Node::new());
+                    nodes[node].next[byte as usize] = new_idx as i32;
+                    new_idx
+                } else {
+                    edge as usize
+                };
+            }
+            nodes[node].output.push(id);
+        }
+
+        let pattern_lens = patterns.iter().map(|p| p.as_ref().len()).collect();
+        let mut ac = Self { nodes, pattern_lens };
+        ac.build_failure_links();
+        ac
+    }
+
+    fn build_failure_links(&mut self) {
+        // BFS from the root. Depth-1 children fail to the root, which is their
+        // initial `fail` value (0), so they are simply enqueued.
+        let mut queue = Vec::new();
+        for byte in 0..256 {
+            let child = self.nodes[0].next[byte];
+            if child < 0 {
+                // Root self-loops on missing transitions so `goto` never fails.
+                self.nodes[0].next[byte] = 0;
+            } else {
+                self.nodes[child as usize].fail = 0;
+                queue.push(child as usize);
+            }
+        }
+
+        let mut head = 0;
+        while head < queue.len() {
+            let node = queue[head];
+            head += 1;
+
+            for byte in 0..256 {
+                let child = self.nodes[node].next[byte];
+                let fail = self.nodes[node].fail as usize;
+                if child < 0 {
+                    // Short-circuit the fail walk by pointing at the fallback.
+                    self.nodes[node].next[byte] = self.nodes[fail].next[byte];
+                } else {
+                    let child = child as usize;
+                    let child_fail = self.nodes[fail].next[byte] as usize;
+                    self.nodes[child].fail = child_fail as i32;
+                    // Union the suffix outputs so dictionary suffixes are reported.
+                    let mut inherited = self.nodes[child_fail].output.clone();
+                    self.nodes[child].output.append(&mut inherited);
+                    queue.push(child);
+                }
+            }
+        }
+    }
+
+    /// Number of patterns the automaton was built with.
+    pub fn pattern_count(&self) -> usize {
+        self.pattern_lens.len()
+    }
+
+    /// Report every match in `text` as (start offset, pattern id), ordered by
+    /// ending position.
+    pub fn find_all(&self, text: &[u8]) -> Vec<Hit> {
+        let mut hits = Vec::new();
+        let mut node = 0usize;
+
+        for (i, &byte) in text.iter().enumerate() {
+            node = self.nodes[node].next[byte as usize] as usize;
+            for &pattern in &self.nodes[node].output {
+                // `i` is the index of the last matched byte; derive the start.
+                let start = i + 1 - self.pattern_lens[pattern];
+                hits.push(Hit { pattern, start });
+            }
+        }
+
+        hits
+    }
+
+    /// Report the leftmost match in `text` (smallest start offset), if any.
+    ///
+    /// Scanning reports matches ordered by their *end* position, but a longer
+    /// pattern ending later can start earlier than a shorter one ending sooner,
+    /// so the first match the scan encounters is not necessarily the leftmost.
+    /// We therefore track the best start seen; we may stop as soon as the scan
+    /// position passes a point where no still-open match could start earlier —
+    /// i.e. once `i >= best_start + max_pattern_len`.
+    pub fn find_first(&self, text: &[u8]) -> Option<Hit> {
+        let max_len = self.pattern_lens.iter().copied().max().unwrap_or(0);
+        let mut node = 0usize;
+        let mut best: Option<Hit> = None;
+        for (i, &byte) in text.iter().enumerate() {
+            if let Some(b) = best {
+                if i >= b.start + max_len {
+                    break;
+                }
+            }
+            node = self.nodes[node].next[byte as usize] as usize;
+            for &pattern in &self.nodes[node].output {
+                let start = i + 1 - self.pattern_lens[pattern];
+                if best.map_or(true, |b| start < b.start) {
+                    best = Some(Hit { pattern, start });
+                }
+            }
+        }
+        best
+    }
+}
+
+/// `StringSearch` backend over the Aho–Corasick automaton. A compiled
+/// [`Config`](StringSearch::Config) is the dictionary of literal byte patterns,
+/// so the `like` layer's already-`%`-stripped literals can be matched in a
+/// single pass; the built [`State`](StringSearch::State) is the automaton.
+pub struct AhoCorasickSearch;
+
+impl StringSearch for AhoCorasickSearch {
+    type Config<'p> = Vec<Vec<u8>>;
+    type State = AhoCorasick;
+
+    fn compile(pattern: &str, _options: CompileOptions) -> Self::Config<'_> {
+        vec![pattern.as_bytes().to_vec()]
+    }
+
+    fn build(config: &Self::Config<'_>) -> Self::State {
+        AhoCorasick::build(config)
+    }
+
+    fn find_bytes(_config: &Self::Config<'_>, state: &Self::State, text: &[u8]) -> Option<usize> {
+        state.find_first(text).map(|hit| hit.start)
+    }
+
+    fn find_all_bytes(_config: &Self::Config<'_>, state: &Self::State, text: &[u8]) -> Vec<usize> {
+        let mut starts: Vec<usize> = state.find_all(text).into_iter().map(|hit| hit.start).collect();
+        starts.sort_unstable();
+        starts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_overlapping_dictionary_suffixes() {
+        let ac = AhoCorasick::build(&["he", "she", "his", "hers"]);
+        let hits = ac.find_all(b"ushers");
+        // "she"@1, "he"@2, "hers"@2 in "ushers".
+        assert!(hits.contains(&Hit { pattern: 1, start: 1 }));
+        assert!(hits.contains(&Hit { pattern: 0, start: 2 }));
+        assert!(hits.contains(&Hit { pattern: 3, start: 2 }));
+    }
+
+    #[test]
+    fn find_first_is_leftmost_by_start() {
+        // "aXY" starts at 0 but ends at 2; "X" ends earlier at 1 but starts at 1.
+        // The leftmost match is "aXY", even though "X" finishes first.
+        let ac = AhoCorasick::build(&["aXY", "X"]);
+        let hit = ac.find_first(b"aXY").unwrap();
+        assert_eq!(hit, Hit { pattern: 0, start: 0 });
+    }
+
+    #[test]
+    fn no_match_returns_empty() {
+        let ac = AhoCorasick::build(&["abc", "def"]);
+        assert!(ac.find_all(b"xyzxyz").is_empty());
+        assert_eq!(ac.find_first(b"xyzxyz"), None);
+    }
+
+    #[test]
+    fn find_first_reports_single_match_start() {
+        let ac = AhoCorasick::build(&["cat", "dog"]);
+        let hit = ac.find_first(b"the dog ran").unwrap();
+        assert_eq!(hit.pattern, 1);
+        assert_eq!(hit.start, 4); // "dog" starts at index 4
+    }
+
+    #[test]
+    fn string_search_backend_finds_literal() {
+        let config = AhoCorasickSearch::compile("STEEL", CompileOptions::default());
+        let state = AhoCorasickSearch::build(&config);
+        let text = b"BRUSHED STEEL BOX";
+        assert_eq!(AhoCorasickSearch::find_bytes(&config, &state, text), Some(8));
+        assert_eq!(
+            AhoCorasickSearch::find_all_bytes(&config, &state, b"STEEL STEEL"),
+            vec![0, 6]
+        );
+    }
+}
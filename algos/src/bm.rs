@@ -1,18 +1,65 @@
-use crate::StringSearch;
+use crate::prefilter;
+use crate::{CompileOptions, StringSearch};
 
 pub struct BM;
 
+/// Precomputed Boyer–Moore state. Built once per pattern, it carries the rare
+/// byte the prefilter scans for so searches over large, mostly-ASCII columns run
+/// at near-`memchr` speed when the pattern has a distinctive byte.
+pub struct BmState {
+    /// Offset and value of the pattern's rarest byte, or `None` when every byte
+    /// is common and the plain sliding-window search is used instead.
+    pivot: Option<(usize, u8)>,
+}
+
 impl StringSearch for BM {
-    type Config = ();
-    type State = ();
+    type Config<'p> = &'p [u8];
+    type State = BmState;
+
+    fn compile(pattern: &str, _options: CompileOptions) -> Self::Config<'_> {
+        pattern.as_bytes()
+    }
+
+    fn build(config: &Self::Config<'_>) -> Self::State {
+        BmState {
+            pivot: prefilter::rare_byte_pivot(config),
+        }
+    }
+
+    fn find_bytes(config: &Self::Config<'_>, state: &Self::State, text: &[u8]) -> Option<usize> {
+        if state.pivot.is_some() {
+            prefilter::find_first_with(text, config, |t, start| window_matches(t, start, config))
+        } else {
+            bm_find(text, config)
+        }
+    }
 
-    fn find_bytes(_state: Self::State, text: &[u8], pattern: &[u8]) -> Option<usize> {
-        bm_find(text, pattern)
+    fn find_all_bytes(config: &Self::Config<'_>, state: &Self::State, text: &[u8]) -> Vec<usize> {
+        if state.pivot.is_some() {
+            prefilter::find_all_with(text, config, |t, start| window_matches(t, start, config))
+        } else {
+            bm_find_all(text, config)
+        }
     }
+}
 
-    fn find_all_bytes(_state: Self::State, text: &[u8], pattern: &[u8]) -> Vec<usize> {
-        bm_find_all(text, pattern)
+/// Verify a candidate alignment with the Boyer–Moore backward comparison loop:
+/// compare the window against the pattern from the last byte toward the first,
+/// so a mismatch at the end rejects immediately.
+#[inline]
+fn window_matches(text: &[u8], start: usize, pattern: &[u8]) -> bool {
+    let m = pattern.len();
+    if start + m > text.len() {
+        return false;
+    }
+    let mut j = m;
+    while j > 0 {
+        j -= 1;
+        if text[start + j] != pattern[j] {
+            return false;
+        }
     }
+    true
 }
 
 /// Build the bad-character shift table for Boyer–Moore.
@@ -197,6 +244,29 @@ mod tests {
         assert_eq!(bm_find_all(hay, pat), vec![0, 3]);
     }
 
+    #[test]
+    fn test_bm_prefilter_matches_plain() {
+        let hay = b"the cat sat on the mat, a cat indeed";
+        let pat = b"cat";
+        let config: &[u8] = pat;
+        let state = BM::build(&config);
+        // 'c' is rarer than the common letters, so the prefilter path is taken.
+        assert!(state.pivot.is_some());
+        assert_eq!(BM::find_bytes(&config, &state, hay), bm_find(hay, pat));
+        assert_eq!(BM::find_all_bytes(&config, &state, hay), bm_find_all(hay, pat));
+    }
+
+    #[test]
+    fn test_bm_prefilter_falls_back_on_common_bytes() {
+        let pat = b"the";
+        let config: &[u8] = pat;
+        let state = BM::build(&config);
+        // Every byte of "the" is common, so no pivot and plain BM is used.
+        assert!(state.pivot.is_none());
+        let hay = b"there is the theme";
+        assert_eq!(BM::find_all_bytes(&config, &state, hay), bm_find_all(hay, pat));
+    }
+
     #[test]
     fn test_bm_utf8() {
         let hay_s = "🌍hello🌍hello";
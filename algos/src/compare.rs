@@ -3,24 +3,227 @@
 pub fn eq_padded_bytes_simd(a: &[u8], b: &[u8]) -> bool {
     assert_eq!(a.len(), b.len(), "Slices must have the same length");
 
-    #[cfg(target_arch = "aarch64")]
+    // With `std` we probe the CPU once and cache the best kernel as a function
+    // pointer, so a single generic-baseline binary still uses AVX2/AVX-512/NEON
+    // on capable hardware — the dispatch model memchr-style crates use.
+    #[cfg(feature = "std")]
+    {
+        return (dispatch::eq_kernel())(a, b);
+    }
+
+    // Without `std` neither `OnceLock` nor `is_*_feature_detected!` is available,
+    // so fall back to the compile-time `target_feature` selection.
+    #[cfg(all(not(feature = "std"), target_arch = "aarch64"))]
     {
-        log::debug!("eq_padded_bytes_simd: using NEON (aarch64)");
         unsafe { arm::eq_padded_bytes_neon(a, b) }
     }
 
-    #[cfg(target_arch = "x86_64")]
+    #[cfg(all(not(feature = "std"), target_arch = "x86_64"))]
     {
         x86::eq_padded_bytes_x86(a, b)
     }
 
-    #[cfg(not(any(target_arch = "aarch64", target_arch = "x86_64")))]
+    #[cfg(all(
+        not(feature = "std"),
+        feature = "portable-simd",
+        not(any(target_arch = "aarch64", target_arch = "x86_64"))
+    ))]
+    {
+        portable::eq_padded_bytes_portable(a, b)
+    }
+
+    #[cfg(all(
+        not(feature = "std"),
+        not(feature = "portable-simd"),
+        not(any(target_arch = "aarch64", target_arch = "x86_64"))
+    ))]
     {
-        debug!("eq_padded_bytes_simd: using scalar fallback (other arch)");
         a == b
     }
 }
 
+/// Count how many bytes of `haystack` equal `needle`. Mirrors the per-arch
+/// structure of [`eq_padded_bytes_simd`]: broadcast the needle, compare a SIMD
+/// block at a time, and reduce the match mask, with a scalar tail.
+pub fn count_eq_bytes_simd(haystack: &[u8], needle: u8) -> usize {
+    #[cfg(feature = "std")]
+    {
+        return (dispatch::count_kernel())(haystack, needle);
+    }
+
+    #[cfg(all(not(feature = "std"), target_arch = "aarch64"))]
+    {
+        return unsafe { arm::count_eq_bytes_neon(haystack, needle) };
+    }
+
+    #[cfg(all(not(feature = "std"), target_arch = "x86_64"))]
+    {
+        return x86::count_eq_bytes_x86(haystack, needle);
+    }
+
+    #[cfg(all(
+        not(feature = "std"),
+        feature = "portable-simd",
+        not(any(target_arch = "aarch64", target_arch = "x86_64"))
+    ))]
+    {
+        portable::count_eq_bytes_portable(haystack, needle)
+    }
+
+    #[cfg(all(
+        not(feature = "std"),
+        not(feature = "portable-simd"),
+        not(any(target_arch = "aarch64", target_arch = "x86_64"))
+    ))]
+    {
+        count_eq_bytes_scalar(haystack, needle)
+    }
+}
+
+/// Runtime CPU-feature dispatch. On first call each kernel probes the host with
+/// `is_x86_feature_detected!` / `is_aarch64_feature_detected!`, picks the widest
+/// implementation the CPU supports, and caches the resulting function pointer in
+/// a [`OnceLock`], so subsequent calls pay no detection cost.
+#[cfg(feature = "std")]
+mod dispatch {
+    use std::sync::OnceLock;
+
+    type EqFn = fn(&[u8], &[u8]) -> bool;
+    type CountFn = fn(&[u8], u8) -> usize;
+
+    static EQ: OnceLock<EqFn> = OnceLock::new();
+    static COUNT: OnceLock<CountFn> = OnceLock::new();
+
+    #[inline]
+    pub fn eq_kernel() -> EqFn {
+        *EQ.get_or_init(select_eq)
+    }
+
+    #[inline]
+    pub fn count_kernel() -> CountFn {
+        *COUNT.get_or_init(select_count)
+    }
+
+    fn eq_scalar(a: &[u8], b: &[u8]) -> bool {
+        a == b
+    }
+
+    fn select_eq() -> EqFn {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx512bw") {
+                log::debug!("eq_padded_bytes_simd: dispatch -> AVX-512BW");
+                return |a, b| unsafe { super::x86::eq_padded_bytes_avx512(a, b) };
+            }
+            if is_x86_feature_detected!("avx2") {
+                log::debug!("eq_padded_bytes_simd: dispatch -> AVX2");
+                return |a, b| unsafe { super::x86::eq_padded_bytes_avx2(a, b) };
+            }
+            if is_x86_feature_detected!("sse4.1") {
+                log::debug!("eq_padded_bytes_simd: dispatch -> SSE4.1");
+                return |a, b| unsafe { super::x86::eq_padded_bytes_sse41(a, b) };
+            }
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                log::debug!("eq_padded_bytes_simd: dispatch -> NEON");
+                return |a, b| unsafe { super::arm::eq_padded_bytes_neon(a, b) };
+            }
+        }
+
+        #[cfg(feature = "portable-simd")]
+        {
+            log::debug!("eq_padded_bytes_simd: dispatch -> portable core::simd");
+            return super::portable::eq_padded_bytes_portable;
+        }
+
+        #[cfg(not(feature = "portable-simd"))]
+        {
+            log::debug!("eq_padded_bytes_simd: dispatch -> scalar");
+            eq_scalar
+        }
+    }
+
+    fn select_count() -> CountFn {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx2") {
+                log::debug!("count_eq_bytes_simd: dispatch -> AVX2");
+                return |h, n| unsafe { super::x86::count_eq_bytes_avx2(h, n) };
+            }
+            if is_x86_feature_detected!("sse4.1") {
+                log::debug!("count_eq_bytes_simd: dispatch -> SSE4.1");
+                return |h, n| unsafe { super::x86::count_eq_bytes_sse41(h, n) };
+            }
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                log::debug!("count_eq_bytes_simd: dispatch -> NEON");
+                return |h, n| unsafe { super::arm::count_eq_bytes_neon(h, n) };
+            }
+        }
+
+        #[cfg(feature = "portable-simd")]
+        {
+            log::debug!("count_eq_bytes_simd: dispatch -> portable core::simd");
+            return super::portable::count_eq_bytes_portable;
+        }
+
+        #[cfg(not(feature = "portable-simd"))]
+        {
+            log::debug!("count_eq_bytes_simd: dispatch -> scalar");
+            super::count_eq_bytes_scalar
+        }
+    }
+}
+
+/// Portable `core::simd` kernels used on targets without a hand-written intrinsic
+/// module (wasm32, riscv64, powerpc, …) when the `portable-simd` feature is on.
+/// Processes fixed-width 32-lane `u8` chunks and handles the remainder with a
+/// scalar tail, so a single codebase vectorizes everywhere `core::simd` lowers.
+#[cfg(feature = "portable-simd")]
+mod portable {
+    use core::simd::{cmp::SimdPartialEq, Simd};
+
+    const LANES: usize = 32;
+
+    pub fn eq_padded_bytes_portable(a: &[u8], b: &[u8]) -> bool {
+        let len = a.len();
+        let mut i = 0;
+        while i + LANES <= len {
+            let va = Simd::<u8, LANES>::from_slice(&a[i..i + LANES]);
+            let vb = Simd::<u8, LANES>::from_slice(&b[i..i + LANES]);
+            if !va.simd_eq(vb).all() {
+                return false;
+            }
+            i += LANES;
+        }
+        a[i..] == b[i..]
+    }
+
+    pub fn count_eq_bytes_portable(haystack: &[u8], needle: u8) -> usize {
+        let len = haystack.len();
+        let mut i = 0;
+        let mut count = 0usize;
+        let vneedle = Simd::<u8, LANES>::splat(needle);
+        while i + LANES <= len {
+            let v = Simd::<u8, LANES>::from_slice(&haystack[i..i + LANES]);
+            count += v.simd_eq(vneedle).to_bitmask().count_ones() as usize;
+            i += LANES;
+        }
+        count + haystack[i..].iter().filter(|&&b| b == needle).count()
+    }
+}
+
+#[inline]
+fn count_eq_bytes_scalar(haystack: &[u8], needle: u8) -> usize {
+    haystack.iter().filter(|&&b| b == needle).count()
+}
+
 #[cfg(target_arch = "aarch64")]
 mod arm {
     use core::arch::aarch64::*;
@@ -63,6 +266,36 @@ mod arm {
 
         true
     }
+
+    /// NEON byte-count: 16 bytes per iteration + scalar tail.
+    /// # Safety
+    /// No preconditions beyond a valid slice.
+    #[inline]
+    pub unsafe fn count_eq_bytes_neon(haystack: &[u8], needle: u8) -> usize {
+        let len = haystack.len();
+        let mut i = 0;
+        let mut count = 0usize;
+
+        let vneedle = unsafe { vdupq_n_u8(needle) };
+        while i + 16 <= len {
+            let p = unsafe { haystack.as_ptr().add(i) };
+            let v = unsafe { vld1q_u8(p) };
+            // 0xFF per equal lane; mask to 1 and horizontally sum the lanes.
+            let eq = unsafe { vceqq_u8(v, vneedle) };
+            let ones = unsafe { vandq_u8(eq, vdupq_n_u8(1)) };
+            count += unsafe { vaddvq_u8(ones) } as usize;
+            i += 16;
+        }
+
+        while i < len {
+            if unsafe { *haystack.get_unchecked(i) } == needle {
+                count += 1;
+            }
+            i += 1;
+        }
+
+        count
+    }
 }
 
 #[cfg(target_arch = "x86_64")]
@@ -107,8 +340,87 @@ mod x86 {
         }
     }
 
+    pub fn count_eq_bytes_x86(haystack: &[u8], needle: u8) -> usize {
+        #[cfg(target_feature = "avx2")]
+        {
+            debug!("count_eq_bytes_simd: using AVX2 implementation");
+            return unsafe { count_eq_bytes_avx2(haystack, needle) };
+        }
+
+        #[cfg(all(not(target_feature = "avx2"), target_feature = "sse4.1"))]
+        {
+            debug!("count_eq_bytes_simd: using SSE4.1 implementation");
+            return unsafe { count_eq_bytes_sse41(haystack, needle) };
+        }
+
+        #[cfg(not(any(target_feature = "avx2", target_feature = "sse4.1")))]
+        {
+            debug!("count_eq_bytes_simd: using scalar fallback (no SIMD features enabled)");
+            super::count_eq_bytes_scalar(haystack, needle)
+        }
+    }
+
     #[target_feature(enable = "sse4.1")]
-    unsafe fn eq_padded_bytes_sse41(a: &[u8], b: &[u8]) -> bool {
+    pub(super) unsafe fn count_eq_bytes_sse41(haystack: &[u8], needle: u8) -> usize {
+        let len = haystack.len();
+        let mut i = 0;
+        let mut count = 0usize;
+        let vneedle = _mm_set1_epi8(needle as i8);
+
+        while i + 16 <= len {
+            let p = unsafe { haystack.as_ptr().add(i) as *const __m128i };
+            let v = unsafe { _mm_loadu_si128(p) };
+            let cmp = _mm_cmpeq_epi8(v, vneedle);
+            count += (_mm_movemask_epi8(cmp) as u16).count_ones() as usize;
+            i += 16;
+        }
+
+        while i < len {
+            if unsafe { *haystack.get_unchecked(i) } == needle {
+                count += 1;
+            }
+            i += 1;
+        }
+
+        count
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn count_eq_bytes_avx2(haystack: &[u8], needle: u8) -> usize {
+        let len = haystack.len();
+        let mut i = 0;
+        let mut count = 0usize;
+        let vneedle = _mm256_set1_epi8(needle as i8);
+
+        while i + 32 <= len {
+            let p = unsafe { haystack.as_ptr().add(i) as *const __m256i };
+            let v = unsafe { _mm256_loadu_si256(p) };
+            let cmp = _mm256_cmpeq_epi8(v, vneedle);
+            count += (_mm256_movemask_epi8(cmp) as u32).count_ones() as usize;
+            i += 32;
+        }
+
+        // 16-byte SSE tail.
+        while i + 16 <= len {
+            let p = unsafe { haystack.as_ptr().add(i) as *const __m128i };
+            let v = unsafe { _mm_loadu_si128(p) };
+            let cmp = _mm_cmpeq_epi8(v, _mm_set1_epi8(needle as i8));
+            count += (_mm_movemask_epi8(cmp) as u16).count_ones() as usize;
+            i += 16;
+        }
+
+        while i < len {
+            if unsafe { *haystack.get_unchecked(i) } == needle {
+                count += 1;
+            }
+            i += 1;
+        }
+
+        count
+    }
+
+    #[target_feature(enable = "sse4.1")]
+    pub(super) unsafe fn eq_padded_bytes_sse41(a: &[u8], b: &[u8]) -> bool {
         let len = a.len();
         let mut i = 0;
 
@@ -140,7 +452,7 @@ mod x86 {
     }
 
     #[target_feature(enable = "avx2")]
-    unsafe fn eq_padded_bytes_avx2(a: &[u8], b: &[u8]) -> bool {
+    pub(super) unsafe fn eq_padded_bytes_avx2(a: &[u8], b: &[u8]) -> bool {
         let len = a.len();
         let mut i = 0;
 
@@ -191,7 +503,7 @@ mod x86 {
     }
 
     #[target_feature(enable = "avx512bw")]
-    unsafe fn eq_padded_bytes_avx512(a: &[u8], b: &[u8]) -> bool {
+    pub(super) unsafe fn eq_padded_bytes_avx512(a: &[u8], b: &[u8]) -> bool {
         let len = a.len();
         let mut i = 0;
 
@@ -294,4 +606,29 @@ mod tests {
         let b = pad_to_multiple_of(64, b"hello neom/x86", 0);
         assert!(!eq_padded_bytes_simd(&a, &b));
     }
+
+    #[test]
+    fn count_eq_bytes_matches_scalar_across_lengths() {
+        // A cheap xorshift keeps the fuzz deterministic without external deps.
+        let mut state = 0x9e37_79b9_u32;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        };
+        for len in 0..200usize {
+            let data: Vec<u8> = (0..len).map(|_| (next() % 4) as u8).collect();
+            for needle in 0u8..4 {
+                let expected = data.iter().filter(|&&b| b == needle).count();
+                // Exercise misaligned starts so the scalar tail is covered too.
+                for start in 0..len.min(5) {
+                    let slice = &data[start..];
+                    let expected = slice.iter().filter(|&&b| b == needle).count();
+                    assert_eq!(count_eq_bytes_simd(slice, needle), expected);
+                }
+                assert_eq!(count_eq_bytes_simd(&data, needle), expected);
+            }
+        }
+    }
 }
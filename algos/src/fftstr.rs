@@ -2,7 +2,7 @@ use std::cell::RefCell;
 use std::cmp;
 use std::ops::{Add, Mul, Sub};
 
-use crate::StringSearch;
+use crate::{CompileOptions, StringSearch};
 
 #[derive(Debug, Clone)]
 pub struct FftConfig {
@@ -48,10 +48,14 @@ pub struct FftStr0;
 pub struct FftStr1;
 
 impl StringSearch for FftStr0 {
-    type Config = FftConfig;
+    type Config<'p> = FftConfig;
     type State = FftState0;
 
-    fn build(config: &Self::Config) -> Self::State {
+    fn compile(pattern: &str, _options: CompileOptions) -> Self::Config<'_> {
+        FftConfig::from_str(pattern)
+    }
+
+    fn build(config: &Self::Config<'_>) -> Self::State {
         let pattern_size = config.pattern.len();
         let required = pattern_size.saturating_mul(3);
 
@@ -81,7 +85,7 @@ impl StringSearch for FftStr0 {
         Self::State { impl_kind }
     }
 
-    fn find_bytes(config: &Self::Config, state: &Self::State, text: &[u8]) -> Option<usize> {
+    fn find_bytes(config: &Self::Config<'_>, state: &Self::State, text: &[u8]) -> Option<usize> {
         match &state.impl_kind {
             ImplKind0::Small(inner) => inner.borrow_mut().find_first(text, config.wildcard),
             ImplKind0::Large(inner) => inner.borrow_mut().find_first(text, config.wildcard),
@@ -90,10 +94,14 @@ impl StringSearch for FftStr0 {
 }
 
 impl StringSearch for FftStr1 {
-    type Config = FftConfig;
+    type Config<'p> = FftConfig;
     type State = FftState1;
 
-    fn build(config: &Self::Config) -> Self::State {
+    fn compile(pattern: &str, _options: CompileOptions) -> Self::Config<'_> {
+        FftConfig::from_str(pattern)
+    }
+
+    fn build(config: &Self::Config<'_>) -> Self::State {
         let pattern_size = config.pattern.len();
         let required = pattern_size.saturating_mul(3);
 
@@ -112,7 +120,7 @@ impl StringSearch for FftStr1 {
         }
     }
 
-    fn find_bytes(config: &Self::Config, state: &Self::State, text: &[u8]) -> Option<usize> {
+    fn find_bytes(config: &Self::Config<'_>, state: &Self::State, text: &[u8]) -> Option<usize> {
         state
             .impl_actual
             .borrow_mut()
@@ -9,12 +9,22 @@ use std::collections::HashSet;
 #[derive(Debug, Clone)]
 pub struct FMIndex {
     text: Vec<u8>,
+    // Full suffix array, kept only on the `sample_rate == 1` fast path. When
+    // `sample_rate > 1` this
+    // is empty and positions are reconstructed from `sa_sample` via LF-mapping.
     sa: Vec<usize>,
     bwt: Vec<u8>,
     c: Vec<usize>,
     counts: Vec<usize>,
     occ: Vec<Vec<u32>>,
     checkpoint: usize,
+    // Suffix-array sampling rate. `SA[i]` is stored explicitly only when
+    // `SA[i] % sample_rate == 0`; other positions are walked back to a sample.
+    sample_rate: usize,
+    // Rows whose suffix-array value is sampled, in row order, with rank support.
+    sampled: BitVec,
+    // `SA[i]` for each sampled row, in ascending row order (`sampled` rank order).
+    sa_sample: Vec<usize>,
     byte_to_rank: [i16; 256],
     rank_to_byte: Vec<u8>,
     sentinel_rank: usize,
@@ -24,7 +34,21 @@ pub struct FMIndex {
 }
 
 impl FMIndex {
-    pub fn new(mut text: Vec<u8>, sentinel: u8, separator: Option<u8>) -> Self {
+    pub fn new(text: Vec<u8>, sentinel: u8, separator: Option<u8>) -> Self {
+        Self::with_sample_rate(text, sentinel, separator, 1)
+    }
+
+    /// Build the index keeping only a sampled suffix array: one in every
+    /// `sample_rate` text positions is stored, the rest reconstructed by walking
+    /// the LF-mapping. `sample_rate == 1` keeps the full suffix array (the old
+    /// behaviour) and takes the direct lookup fast path in [`search`](Self::search).
+    pub fn with_sample_rate(
+        mut text: Vec<u8>,
+        sentinel: u8,
+        separator: Option<u8>,
+        sample_rate: usize,
+    ) -> Self {
+        let sample_rate = sample_rate.max(1);
         if let Some(sep) = separator {
             assert!(sep != sentinel, "separator must differ from sentinel");
         }
@@ -56,6 +80,25 @@ impl FMIndex {
         let checkpoint = 128usize;
         let occ = build_occ(&bwt, counts.len(), checkpoint);
 
+        // Sample the suffix array. On the fast path (`sample_rate == 1`) keep the
+        // full `sa`; otherwise record only rows whose SA value is a multiple of
+        // the rate and drop the rest, leaving `locate` to walk the LF-mapping.
+        let n = sa.len();
+        let mut sampled = BitVec::new(n);
+        let mut sa_sample = Vec::new();
+        let sa = if sample_rate == 1 {
+            sa
+        } else {
+            for (row, &pos) in sa.iter().enumerate() {
+                if pos % sample_rate == 0 {
+                    sampled.set(row);
+                    sa_sample.push(pos);
+                }
+            }
+            sampled.build_rank();
+            Vec::new()
+        };
+
         Self {
             text,
             sa,
@@ -64,6 +107,9 @@ impl FMIndex {
             counts,
             occ,
             checkpoint,
+            sample_rate,
+            sampled,
+            sa_sample,
             byte_to_rank,
             rank_to_byte,
             sentinel_rank,
@@ -108,7 +154,7 @@ impl FMIndex {
     pub fn search(&self, pattern: &[u8]) -> Vec<usize> {
         match self.backward_search(pattern) {
             Some((top, bottom)) => {
-                let mut out = self.sa[top..bottom].to_vec();
+                let mut out: Vec<usize> = (top..bottom).map(|i| self.locate(i)).collect();
                 out.sort_unstable();
                 out
             }
@@ -116,6 +162,25 @@ impl FMIndex {
         }
     }
 
+    /// Text position of BWT row `i`. On the full-SA fast path this is a direct
+    /// lookup; otherwise it walks `LF(i) = c[bwt[i]] + occ_at(bwt[i], i)` until it
+    /// lands on a sampled row, then offsets the stored position by the step count.
+    /// Termination is guaranteed because position `0` is always a multiple of the
+    /// sample rate, and the walk strictly decreases the text position each step.
+    fn locate(&self, mut i: usize) -> usize {
+        if self.sample_rate == 1 {
+            return self.sa[i];
+        }
+        let mut steps = 0usize;
+        while !self.sampled.get(i) {
+            let rank = self.bwt[i] as usize;
+            i = self.c[rank] + self.occ_at(rank, i);
+            steps += 1;
+        }
+        let pos = self.sa_sample[self.sampled.rank1(i)];
+        (pos + steps) % self.len()
+    }
+
     pub fn search_with_underscore(&self, pattern: &[u8]) -> Vec<usize> {
         if pattern.is_empty() {
             return (0..self.len()).collect();
@@ -132,8 +197,8 @@ impl FMIndex {
             results: &mut HashSet<usize>,
         ) {
             if idx < 0 {
-                for &pos in &fm.sa[top..bottom] {
-                    results.insert(pos);
+                for i in top..bottom {
+                    results.insert(fm.locate(i));
                 }
                 return;
             }
@@ -195,17 +260,195 @@ impl FMIndex {
         out
     }
 
+    /// All text positions matching `pattern` within Hamming distance `k`.
+    ///
+    /// Generalizes [`Self::search_with_underscore`]: the backward walk carries a
+    /// `budget` of remaining substitutions. At each pattern character it descends
+    /// the exact-matching rank with the budget unchanged, and — when the budget is
+    /// positive — also descends every *other* rank in the current `[top, bottom)`
+    /// range (exactly like the wildcard branch) with `budget - 1`. Substitution
+    /// never crosses the sentinel/separator, so a match can never span two rows,
+    /// and empty intervals (`new_top >= new_bottom`) are pruned. `k == 0` reduces
+    /// to exact [`Self::search`].
+    pub fn search_with_mismatches(&self, pattern: &[u8], k: usize) -> Vec<usize> {
+        if pattern.is_empty() {
+            return (0..self.len()).collect();
+        }
+
+        let mut results = HashSet::new();
+
+        #[allow(clippy::too_many_arguments)]
+        fn rec(
+            fm: &FMIndex,
+            pattern: &[u8],
+            idx: isize,
+            top: usize,
+            bottom: usize,
+            budget: usize,
+            results: &mut HashSet<usize>,
+        ) {
+            if idx < 0 {
+                for i in top..bottom {
+                    results.insert(fm.locate(i));
+                }
+                return;
+            }
+
+            let ch = pattern[idx as usize];
+
+            // Exact-match descent: spend no budget.
+            if let Some(rank) = fm.rank_for_byte(ch) {
+                if fm.counts[rank] != 0 {
+                    let new_top = fm.c[rank] + fm.occ_at(rank, top);
+                    let new_bottom = fm.c[rank] + fm.occ_at(rank, bottom);
+                    if new_top < new_bottom {
+                        rec(fm, pattern, idx - 1, new_top, new_bottom, budget, results);
+                    }
+                }
+            }
+
+            // Substitution descent: spend one unit of budget per differing byte,
+            // skipping the exact rank (covered above) and the row delimiters.
+            if budget > 0 {
+                let exact = fm.rank_for_byte(ch);
+                let mut seen = vec![false; fm.counts.len()];
+                for &rank in &fm.bwt[top..bottom] {
+                    let r = rank as usize;
+                    if seen[r] {
+                        continue;
+                    }
+                    seen[r] = true;
+                    if Some(r) == exact {
+                        continue;
+                    }
+                    if r == fm.sentinel_rank {
+                        continue;
+                    }
+                    if let Some(sep_rank) = fm.separator_rank {
+                        if r == sep_rank {
+                            continue;
+                        }
+                    }
+                    if fm.counts[r] == 0 {
+                        continue;
+                    }
+
+                    let new_top = fm.c[r] + fm.occ_at(r, top);
+                    let new_bottom = fm.c[r] + fm.occ_at(r, bottom);
+                    if new_top < new_bottom {
+                        rec(fm, pattern, idx - 1, new_top, new_bottom, budget - 1, results);
+                    }
+                }
+            }
+        }
+
+        rec(
+            self,
+            pattern,
+            (pattern.len() as isize) - 1,
+            0,
+            self.len(),
+            k,
+            &mut results,
+        );
+
+        let mut out: Vec<usize> = results.into_iter().collect();
+        out.sort_unstable();
+        out
+    }
+
+    /// Search a batch of patterns in a single shared traversal.
+    ///
+    /// Backward search consumes each pattern right-to-left, so patterns sharing a
+    /// suffix share a prefix once reversed. We build a trie keyed on the reversed
+    /// patterns and walk it depth-first: each trie edge advances the `[top, bottom)`
+    /// interval by one LF step, and a node terminating pattern `p` emits the located
+    /// positions of the current interval for `p`. This amortizes the `occ_at` work
+    /// across patterns with common suffixes — the "match many needles in one pass"
+    /// idea behind Aho-Corasick. Branches whose interval empties are pruned, so a
+    /// shared suffix that occurs nowhere abandons a whole group early. Results come
+    /// back one vector per input pattern, in input order.
+    pub fn search_many(&self, patterns: &[&[u8]]) -> Vec<Vec<usize>> {
+        // Trie node: child edges keyed by byte, plus the ids of patterns ending here.
+        struct Node {
+            children: Vec<(u8, usize)>,
+            terminals: Vec<usize>,
+        }
+        impl Node {
+            fn new() -> Self {
+                Node {
+                    children: Vec::new(),
+                    terminals: Vec::new(),
+                }
+            }
+        }
+
+        let mut trie: Vec<Node> = vec![Node::new()];
+        for (id, pat) in patterns.iter().enumerate() {
+            let mut node = 0usize;
+            for &ch in pat.iter().rev() {
+                let next = trie[node].children.iter().find(|&&(b, _)| b == ch).map(|&(_, n)| n);
+                node = match next {
+                    Some(n) => n,
+                    None => {
+                        let n = trie.len();
+                        trie.push(Node::new());
+                        trie[node].children.push((ch, n));
+                        n
+                    }
+                };
+            }
+            trie[node].terminals.push(id);
+        }
+
+        let mut results = vec![Vec::new(); patterns.len()];
+
+        fn walk(
+            fm: &FMIndex,
+            trie: &[Node],
+            node: usize,
+            top: usize,
+            bottom: usize,
+            results: &mut [Vec<usize>],
+        ) {
+            if !trie[node].terminals.is_empty() {
+                let located: Vec<usize> = (top..bottom).map(|i| fm.locate(i)).collect();
+                for &id in &trie[node].terminals {
+                    results[id].extend_from_slice(&located);
+                }
+            }
+            for &(ch, child) in &trie[node].children {
+                let rank = match fm.rank_for_byte(ch) {
+                    Some(rank) => rank,
+                    None => continue,
+                };
+                if fm.counts[rank] == 0 {
+                    continue;
+                }
+                let new_top = fm.c[rank] + fm.occ_at(rank, top);
+                let new_bottom = fm.c[rank] + fm.occ_at(rank, bottom);
+                if new_top < new_bottom {
+                    walk(fm, trie, child, new_top, new_bottom, results);
+                }
+            }
+        }
+
+        walk(self, &trie, 0, 0, self.len(), &mut results);
+
+        for r in &mut results {
+            r.sort_unstable();
+        }
+        results
+    }
+
     fn occ_at(&self, rank: usize, index: usize) -> usize {
         let capped = index.min(self.len());
         let base_idx = capped / self.checkpoint;
         let base_pos = base_idx * self.checkpoint;
-        let mut count = self.occ[base_idx][rank] as usize;
-        for &r in &self.bwt[base_pos..capped] {
-            if r as usize == rank {
-                count += 1;
-            }
-        }
-        count
+        let count = self.occ[base_idx][rank] as usize;
+        // The BWT is remapped to small ranks (single bytes), so a vectorized
+        // byte-count over the checkpoint block replaces the scalar tally.
+        count + crate::compare::count_eq_bytes_simd(&self.bwt[base_pos..capped], rank as u8)
     }
 
     fn rank_for_byte(&self, ch: u8) -> Option<usize> {
@@ -218,9 +461,162 @@ impl FMIndex {
     }
 }
 
+/// Suffix array of `text` via the SA-IS induced-sorting algorithm, linear in
+/// `text.len()`. `text` is expected to end in the unique smallest sentinel byte
+/// (the `text.push(sentinel)` contract), which anchors the induced sort.
 fn build_suffix_array(text: &[u8]) -> Vec<usize> {
-    let mut sa: Vec<usize> = (0..text.len()).collect();
-    sa.sort_by(|&a, &b| text[a..].cmp(&text[b..]));
+    let s: Vec<usize> = text.iter().map(|&b| b as usize).collect();
+    sais(&s, 256)
+}
+
+#[inline]
+fn is_lms(t: &[bool], i: usize) -> bool {
+    i > 0 && t[i] && !t[i - 1]
+}
+
+fn bucket_starts(s: &[usize], sigma: usize) -> Vec<usize> {
+    let mut starts = vec![0usize; sigma];
+    for &c in s {
+        starts[c] += 1;
+    }
+    let mut sum = 0;
+    for slot in starts.iter_mut() {
+        let count = *slot;
+        *slot = sum;
+        sum += count;
+    }
+    starts
+}
+
+fn bucket_ends(s: &[usize], sigma: usize) -> Vec<usize> {
+    let mut ends = vec![0usize; sigma];
+    for &c in s {
+        ends[c] += 1;
+    }
+    let mut sum = 0;
+    for slot in ends.iter_mut() {
+        sum += *slot;
+        *slot = sum;
+    }
+    ends
+}
+
+/// Induce the L-type suffixes left-to-right from bucket heads, then the S-type
+/// suffixes right-to-left from bucket tails, given LMS suffixes already placed.
+fn induce(sa: &mut [usize], s: &[usize], t: &[bool], sigma: usize) {
+    let n = s.len();
+    let mut heads = bucket_starts(s, sigma);
+    for i in 0..n {
+        let j = sa[i];
+        if j != usize::MAX && j > 0 && !t[j - 1] {
+            let c = s[j - 1];
+            sa[heads[c]] = j - 1;
+            heads[c] += 1;
+        }
+    }
+    let mut tails = bucket_ends(s, sigma);
+    for i in (0..n).rev() {
+        let j = sa[i];
+        if j != usize::MAX && j > 0 && t[j - 1] {
+            let c = s[j - 1];
+            tails[c] -= 1;
+            sa[tails[c]] = j - 1;
+        }
+    }
+}
+
+fn sais(s: &[usize], sigma: usize) -> Vec<usize> {
+    let n = s.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    if n == 1 {
+        return vec![0];
+    }
+
+    // Classify suffix types: S-type if smaller than its successor (the sentinel
+    // at the end is S-type by definition).
+    let mut t = vec![false; n];
+    t[n - 1] = true;
+    for i in (0..n - 1).rev() {
+        t[i] = s[i] < s[i + 1] || (s[i] == s[i + 1] && t[i + 1]);
+    }
+
+    // Pass 1: place LMS suffixes at their bucket tails, then induce.
+    let mut sa = vec![usize::MAX; n];
+    let mut tails = bucket_ends(s, sigma);
+    for i in (0..n).rev() {
+        if is_lms(&t, i) {
+            let c = s[i];
+            tails[c] -= 1;
+            sa[tails[c]] = i;
+        }
+    }
+    induce(&mut sa, s, &t, sigma);
+
+    // Name the LMS substrings in the order the induced sort left them.
+    let lms_equal = |a: usize, b: usize| -> bool {
+        let mut i = 0;
+        loop {
+            let (ai, bi) = (a + i, b + i);
+            if ai >= n || bi >= n {
+                return false;
+            }
+            if s[ai] != s[bi] || t[ai] != t[bi] {
+                return false;
+            }
+            let (a_lms, b_lms) = (is_lms(&t, ai), is_lms(&t, bi));
+            if i > 0 && a_lms && b_lms {
+                return true;
+            }
+            if i > 0 && a_lms != b_lms {
+                return false;
+            }
+            i += 1;
+        }
+    };
+
+    let mut names = vec![usize::MAX; n];
+    let mut name = 0usize;
+    let mut prev: Option<usize> = None;
+    for i in 0..n {
+        let j = sa[i];
+        if j != usize::MAX && is_lms(&t, j) {
+            if let Some(p) = prev {
+                if !lms_equal(p, j) {
+                    name += 1;
+                }
+            }
+            names[j] = name;
+            prev = Some(j);
+        }
+    }
+
+    // Reduced problem: LMS names in text order.
+    let lms_positions: Vec<usize> = (0..n).filter(|&i| is_lms(&t, i)).collect();
+    let reduced: Vec<usize> = lms_positions.iter().map(|&p| names[p]).collect();
+
+    let reduced_sa = if name + 1 == reduced.len() {
+        // All names unique: the suffix array of the reduced string is its inverse.
+        let mut rsa = vec![0usize; reduced.len()];
+        for (i, &nm) in reduced.iter().enumerate() {
+            rsa[nm] = i;
+        }
+        rsa
+    } else {
+        sais(&reduced, name + 1)
+    };
+
+    // Pass 2: place the now fully ordered LMS suffixes and re-induce.
+    let mut sa = vec![usize::MAX; n];
+    let mut tails = bucket_ends(s, sigma);
+    for &idx in reduced_sa.iter().rev() {
+        let p = lms_positions[idx];
+        let c = s[p];
+        tails[c] -= 1;
+        sa[tails[c]] = p;
+    }
+    induce(&mut sa, s, &t, sigma);
     sa
 }
 
@@ -302,6 +698,53 @@ fn build_occ(bwt: &[u8], sigma: usize, checkpoint: usize) -> Vec<Vec<u32>> {
     occ
 }
 
+/// Bit array with a one-level rank index, used to mark the rows whose
+/// suffix-array value is sampled. `rank1` reports the number of sampled rows
+/// strictly before a given row, which is the index into `sa_sample`.
+#[derive(Debug, Clone, Default)]
+struct BitVec {
+    words: Vec<u64>,
+    // Cumulative set-bit count before each 64-bit word; one entry per word.
+    block_ranks: Vec<usize>,
+}
+
+impl BitVec {
+    fn new(n: usize) -> Self {
+        Self {
+            words: vec![0u64; n.div_ceil(64)],
+            block_ranks: Vec::new(),
+        }
+    }
+
+    fn set(&mut self, i: usize) {
+        self.words[i / 64] |= 1u64 << (i % 64);
+    }
+
+    #[inline]
+    fn get(&self, i: usize) -> bool {
+        self.words[i / 64] & (1u64 << (i % 64)) != 0
+    }
+
+    /// Precompute per-word prefix popcounts so `rank1` is O(1).
+    fn build_rank(&mut self) {
+        self.block_ranks = Vec::with_capacity(self.words.len());
+        let mut total = 0usize;
+        for &word in &self.words {
+            self.block_ranks.push(total);
+            total += word.count_ones() as usize;
+        }
+    }
+
+    /// Number of set bits in `[0, i)`.
+    #[inline]
+    fn rank1(&self, i: usize) -> usize {
+        let word = i / 64;
+        let bit = i % 64;
+        let partial = (self.words[word] & ((1u64 << bit) - 1)).count_ones() as usize;
+        self.block_ranks[word] + partial
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::FMIndex;
@@ -334,4 +777,95 @@ mod tests {
         let matches = fm.search_with_underscore(b"a__le");
         assert_eq!(matches, vec![15]);
     }
+
+    #[test]
+    fn test_mismatches_is_exact_at_zero() {
+        let fm = sample_index();
+        for q in [b"ana".as_slice(), b"ban", b"apple", b"zzz"] {
+            assert_eq!(fm.search_with_mismatches(q, 0), fm.search(q), "{q:?}");
+        }
+    }
+
+    #[test]
+    fn test_mismatches_matches_naive() {
+        // Brute-force oracle: a start matches if its length-`m` window lies inside
+        // one row (never straddling a separator/sentinel) and differs from the
+        // pattern in at most `k` positions.
+        fn naive(text: &[u8], pattern: &[u8], k: usize) -> Vec<usize> {
+            let m = pattern.len();
+            let mut out = Vec::new();
+            for start in 0..text.len().saturating_sub(m - 1) {
+                let window = &text[start..start + m];
+                if window.iter().any(|&b| b == SENTINEL || b == SEP) {
+                    continue;
+                }
+                let mism = window.iter().zip(pattern).filter(|(a, b)| a != b).count();
+                if mism <= k {
+                    out.push(start);
+                }
+            }
+            out.sort_unstable();
+            out
+        }
+
+        let raw = b"banana\x1fbandana\x1fapple";
+        let fm = FMIndex::new(raw.to_vec(), SENTINEL, Some(SEP));
+        for q in [b"ana".as_slice(), b"ban", b"apple", b"xxx"] {
+            for k in 0..=2 {
+                assert_eq!(
+                    fm.search_with_mismatches(q, k),
+                    naive(raw, q, k),
+                    "query {q:?} k {k}",
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_search_many_matches_individual() {
+        let fm = sample_index();
+        let patterns: &[&[u8]] = &[b"ana", b"ban", b"na", b"zzz", b"apple"];
+        let batched = fm.search_many(patterns);
+        assert_eq!(batched.len(), patterns.len());
+        for (p, got) in patterns.iter().zip(&batched) {
+            assert_eq!(got, &fm.search(p), "pattern {p:?}");
+        }
+    }
+
+    #[test]
+    fn test_sais_matches_naive() {
+        fn naive(text: &[u8]) -> Vec<usize> {
+            let mut sa: Vec<usize> = (0..text.len()).collect();
+            sa.sort_by(|&a, &b| text[a..].cmp(&text[b..]));
+            sa
+        }
+        let cases: &[&[u8]] = &[
+            b"\x00",
+            b"a\x00",
+            b"banana\x00",
+            b"mississippi\x00",
+            b"abracadabra\x00",
+            b"aaaaaa\x00",
+            b"the quick brown fox\x00",
+        ];
+        for &text in cases {
+            assert_eq!(super::build_suffix_array(text), naive(text), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn test_sampled_matches_full() {
+        let text = b"banana\x1fbandana\x1fapple".to_vec();
+        let full = FMIndex::new(text.clone(), SENTINEL, Some(SEP));
+        for rate in [2usize, 3, 4, 8] {
+            let sampled = FMIndex::with_sample_rate(text.clone(), SENTINEL, Some(SEP), rate);
+            for q in [b"ana".as_slice(), b"ban", b"apple", b"zzz"] {
+                assert_eq!(sampled.search(q), full.search(q), "rate {rate} query {q:?}");
+            }
+            assert_eq!(
+                sampled.search_with_underscore(b"b_n"),
+                full.search_with_underscore(b"b_n"),
+            );
+        }
+    }
 }
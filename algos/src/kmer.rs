@@ -3,12 +3,32 @@ use std::{
     sync::Arc,
 };
 
-use crate::StringSearch;
+use crate::{CompileOptions, StringSearch};
+
+/// Default seed length used when a `KmerSearch` config is produced through the
+/// generic [`StringSearch::compile`] entry point, which only sees the pattern.
+/// Callers that need a specific `k`/`min_hits` build a [`KmerConfig`] directly.
+pub const DEFAULT_KMER_K: usize = 4;
 
 pub struct KmerConfig {
     pub pattern: Vec<u8>,
     pub k: usize,
     pub min_hits: usize,
+    /// Maximum number of mismatched bytes tolerated in the verified alignment.
+    /// `0` keeps the exact-match fast path; larger values give a fuzzy
+    /// "approximately contains" LIKE.
+    pub max_mismatch: usize,
+    /// Bottom-`s` MinHash sketch size. `0` disables the sketch; the index then
+    /// carries no cheap containment estimator.
+    pub sketch_size: usize,
+}
+
+/// Bottom-`s` MinHash sketch: the `s` smallest distinct k-mer hashes, kept
+/// sorted ascending so two sketches merge in O(s). A sketch of fewer than `s`
+/// values is the exact hash set of a small k-mer collection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sketch {
+    hashes: Vec<u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -19,17 +39,200 @@ pub struct KmerIndex {
 #[derive(Debug)]
 pub struct KmerIndexInner {
     map: HashMap<Vec<u8>, Vec<usize>>,
+    // The pattern is kept so the extension phase can verify a seeded diagonal
+    // rather than trusting the seed count alone.
+    pattern: Vec<u8>,
     k: usize,
     min_hits: usize,
+    max_mismatch: usize,
+    // Bottom-`s` size and the pattern's own sketch, absent when `sketch_size` is 0.
+    sketch_size: usize,
+    sketch: Option<Sketch>,
+}
+
+/// 64-bit FNV-1a, the fixed hash shared by every sketch so pattern and text
+/// hashes are directly comparable.
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+fn hash_kmer(kmer: &[u8]) -> u64 {
+    let mut h = FNV_OFFSET;
+    for &b in kmer {
+        h ^= b as u64;
+        h = h.wrapping_mul(FNV_PRIME);
+    }
+    h
+}
+
+/// Hash every `k`-mer of `data` and keep the `s` smallest distinct values.
+fn sketch_bytes(data: &[u8], k: usize, s: usize) -> Sketch {
+    let mut hashes: Vec<u64> = Vec::new();
+    if k > 0 && data.len() >= k {
+        for i in 0..=data.len() - k {
+            hashes.push(hash_kmer(&data[i..i + k]));
+        }
+    }
+    hashes.sort_unstable();
+    hashes.dedup();
+    if hashes.len() > s {
+        hashes.truncate(s);
+    }
+    Sketch { hashes }
+}
+
+/// Estimate the containment `|A ∩ B| / |A|` of the pattern sketch `A` in the
+/// text sketch `B`. Merges the two sorted sketches, takes the `s` smallest
+/// unique hashes overall (`s` being the longer sketch's length, which is the
+/// sketch size whenever either sketch is full), and counts those present in
+/// both sketches.
+pub fn estimate_containment(pattern_sketch: &Sketch, text_sketch: &Sketch) -> f64 {
+    let denom = pattern_sketch.hashes.len();
+    if denom == 0 {
+        return 0.0;
+    }
+
+    // Bottom-s of the union, walking both sorted sketches at once.
+    let (a, b) = (&pattern_sketch.hashes, &text_sketch.hashes);
+    let limit = a.len().max(b.len());
+    let mut intersection = 0usize;
+    let (mut i, mut j, mut taken) = (0usize, 0usize, 0usize);
+    while taken < limit && (i < a.len() || j < b.len()) {
+        let next = match (a.get(i), b.get(j)) {
+            (Some(&x), Some(&y)) => {
+                if x == y {
+                    // Present in both: an intersection hit within the bottom-s.
+                    intersection += 1;
+                    i += 1;
+                    j += 1;
+                    x
+                } else if x < y {
+                    i += 1;
+                    x
+                } else {
+                    j += 1;
+                    y
+                }
+            }
+            (Some(&x), None) => {
+                i += 1;
+                x
+            }
+            (None, Some(&y)) => {
+                j += 1;
+                y
+            }
+            (None, None) => break,
+        };
+        let _ = next;
+        taken += 1;
+    }
+
+    intersection as f64 / denom as f64
+}
+
+/// Register precision used by [`estimate_distinct_kmers`]: `m = 2^14 = 16384`
+/// one-byte registers (~16 KiB), the usual accuracy/size trade-off.
+pub const HLL_PRECISION: usize = 14;
+
+/// HyperLogLog cardinality estimator over 64-bit k-mer hashes. Near-constant
+/// memory (`2^p` byte registers) regardless of how many distinct k-mers a
+/// column holds, and two estimators combine by register-wise max so per-shard
+/// counts loaded from a [`BumpArena`](../../storage) can be merged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HyperLogLog {
+    p: usize,
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    /// New all-zero estimator with `2^p` registers.
+    pub fn new(p: usize) -> Self {
+        assert!((4..=16).contains(&p), "HyperLogLog precision out of range");
+        Self {
+            p,
+            registers: vec![0u8; 1 << p],
+        }
+    }
+
+    /// Fold one 64-bit hash in: the top `p` bits pick the register, the rest
+    /// contribute `1 + leading_zeros`.
+    pub fn add_hash(&mut self, hash: u64) {
+        let index = (hash >> (64 - self.p)) as usize;
+        let remaining = hash << self.p;
+        let rho = if remaining == 0 {
+            (64 - self.p + 1) as u8
+        } else {
+            remaining.leading_zeros() as u8 + 1
+        };
+        if rho > self.registers[index] {
+            self.registers[index] = rho;
+        }
+    }
+
+    /// Merge `other` into `self` by taking the larger value of each register.
+    /// Both estimators must share the same precision.
+    pub fn merge(&mut self, other: &HyperLogLog) {
+        assert_eq!(self.p, other.p, "cannot merge HyperLogLogs of differing precision");
+        for (reg, &val) in self.registers.iter_mut().zip(other.registers.iter()) {
+            if val > *reg {
+                *reg = val;
+            }
+        }
+    }
+
+    /// Bias-corrected distinct-count estimate, with the linear-counting
+    /// correction applied in the small-cardinality range.
+    pub fn estimate(&self) -> u64 {
+        let m = self.registers.len() as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|&r| 2f64.powi(-(r as i32)))
+            .sum();
+        let raw = alpha * m * m / sum;
+
+        let zeros = self.registers.iter().filter(|&&r| r == 0).count();
+        if raw <= 2.5 * m && zeros > 0 {
+            // Small range: linear counting over empty registers is more accurate.
+            (m * (m / zeros as f64).ln()).round() as u64
+        } else {
+            raw.round() as u64
+        }
+    }
+}
+
+/// Estimate the number of *distinct* `k`-mers in `text` with a HyperLogLog at
+/// the default [`HLL_PRECISION`]. A proxy for how selective a LIKE pattern will
+/// be against the column.
+pub fn estimate_distinct_kmers(text: &[u8], k: usize) -> u64 {
+    let mut hll = HyperLogLog::new(HLL_PRECISION);
+    if k > 0 && text.len() >= k {
+        for i in 0..=text.len() - k {
+            hll.add_hash(hash_kmer(&text[i..i + k]));
+        }
+    }
+    hll.estimate()
 }
 
 pub struct KmerSearch;
 
 impl StringSearch for KmerSearch {
-    type Config = KmerConfig;
+    type Config<'p> = KmerConfig;
     type State = KmerIndex;
 
-    fn build(config: Self::Config) -> Self::State {
+    fn compile(pattern: &str, _options: CompileOptions) -> Self::Config<'_> {
+        KmerConfig {
+            pattern: pattern.as_bytes().to_vec(),
+            k: DEFAULT_KMER_K,
+            min_hits: 1,
+            max_mismatch: 0,
+            sketch_size: 0,
+        }
+    }
+
+    fn build(config: &Self::Config<'_>) -> Self::State {
         let mut map = HashMap::<Vec<u8>, Vec<usize>>::new();
         let k = config.k;
 
@@ -40,10 +243,20 @@ impl StringSearch for KmerSearch {
             }
         }
 
+        let sketch = if config.sketch_size > 0 {
+            Some(sketch_bytes(&config.pattern, config.k, config.sketch_size))
+        } else {
+            None
+        };
+
         let inner = KmerIndexInner {
             map,
+            pattern: config.pattern.clone(),
             k: config.k,
             min_hits: config.min_hits,
+            max_mismatch: config.max_mismatch,
+            sketch_size: config.sketch_size,
+            sketch,
         };
 
         KmerIndex {
@@ -51,47 +264,72 @@ impl StringSearch for KmerSearch {
         }
     }
 
-    fn find_bytes(state: &Self::State, text: &[u8], _pattern: &[u8]) -> Option<usize> {
-        let state = state.inner.clone();
-        if state.map.is_empty() || text.len() < state.k {
-            return None;
-        }
-
-        let mut diagonal_counts: HashMap<isize, usize> = HashMap::new();
-
-        for text_pos in 0..=text.len() - state.k {
-            let kmer = &text[text_pos..text_pos + state.k];
-
-            if let Some(query_positions) = state.map.get(kmer) {
-                for &query_pos in query_positions {
-                    let diagonal = text_pos as isize - query_pos as isize;
+    fn find_bytes(config: &Self::Config<'_>, state: &Self::State, text: &[u8]) -> Option<usize> {
+        Self::find_with_mismatches(config, state, text).map(|(pos, _)| pos)
+    }
 
-                    if diagonal < 0 {
-                        continue;
-                    }
+    fn find_all_bytes(config: &Self::Config<'_>, state: &Self::State, text: &[u8]) -> Vec<usize> {
+        Self::find_all_with_mismatches(config, state, text)
+            .into_iter()
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+}
 
-                    let count = diagonal_counts.entry(diagonal).or_insert(0);
-                    *count += 1;
+impl KmerIndex {
+    /// The pattern's own MinHash sketch, if the index was built with
+    /// `sketch_size > 0`.
+    pub fn pattern_sketch(&self) -> Option<&Sketch> {
+        self.inner.sketch.as_ref()
+    }
 
-                    if *count >= state.min_hits {
-                        return Some(diagonal as usize);
-                    }
-                }
-            }
+    /// Sketch `text` with the same hash and `k` as the pattern so the two
+    /// sketches can be compared with [`estimate_containment`]. Returns an empty
+    /// sketch when the index carries no sketch.
+    pub fn sketch_text(&self, text: &[u8]) -> Sketch {
+        if self.inner.sketch_size == 0 {
+            return Sketch { hashes: Vec::new() };
         }
+        sketch_bytes(text, self.inner.k, self.inner.sketch_size)
+    }
+
+    /// Estimate the distinct-k-mer count of `text` using this index's `k`.
+    pub fn estimate_distinct(&self, text: &[u8]) -> u64 {
+        estimate_distinct_kmers(text, self.inner.k)
+    }
+}
 
-        None
+impl KmerSearch {
+    /// Seed with shared k-mers, then extend: the first diagonal that gathers
+    /// `min_hits` seeds *and* verifies within `max_mismatch` is returned as
+    /// `(start, mismatches)`. Returns the leftmost such position.
+    pub fn find_with_mismatches(
+        _config: &KmerConfig,
+        state: &KmerIndex,
+        text: &[u8],
+    ) -> Option<(usize, usize)> {
+        Self::find_all_with_mismatches(_config, state, text)
+            .into_iter()
+            .next()
     }
 
-    fn find_all_bytes(state: &Self::State, text: &[u8], _pattern: &[u8]) -> Vec<usize> {
+    /// All verified alignments as `(start, mismatches)`, sorted by position and
+    /// deduplicated per diagonal.
+    pub fn find_all_with_mismatches(
+        _config: &KmerConfig,
+        state: &KmerIndex,
+        text: &[u8],
+    ) -> Vec<(usize, usize)> {
         let state = state.inner.clone();
         if state.map.is_empty() || text.len() < state.k {
             return Vec::new();
         }
 
         let mut diagonal_counts: HashMap<isize, usize> = HashMap::new();
-        let mut found_diagonals: HashSet<isize> = HashSet::new();
-        let mut results = Vec::new();
+        // Each diagonal is verified at most once, the moment its seed count
+        // first reaches `min_hits`, so overlapping seeds don't re-report it.
+        let mut settled: HashSet<isize> = HashSet::new();
+        let mut results: Vec<(usize, usize)> = Vec::new();
 
         for text_pos in 0..=text.len() - state.k {
             let kmer = &text[text_pos..text_pos + state.k];
@@ -107,20 +345,49 @@ impl StringSearch for KmerSearch {
                     let count = diagonal_counts.entry(diagonal).or_insert(0);
                     *count += 1;
 
-                    if *count >= state.min_hits {
-                        if found_diagonals.insert(diagonal) {
-                            results.push(diagonal as usize);
+                    if *count == state.min_hits && settled.insert(diagonal) {
+                        if let Some(mismatches) =
+                            verify_diagonal(&state.pattern, text, diagonal as usize, state.max_mismatch)
+                        {
+                            results.push((diagonal as usize, mismatches));
                         }
                     }
                 }
             }
         }
 
-        results.sort();
+        results.sort_by_key(|&(pos, _)| pos);
         results
     }
 }
 
+/// Anchor the pattern at `start` in `text` and count mismatching bytes, bailing
+/// out as soon as the count exceeds `max_mismatch`. Returns `None` when the
+/// window runs past the end of `text` or too many bytes differ.
+fn verify_diagonal(
+    pattern: &[u8],
+    text: &[u8],
+    start: usize,
+    max_mismatch: usize,
+) -> Option<usize> {
+    let m = pattern.len();
+    if start + m > text.len() {
+        return None;
+    }
+
+    let mut mismatches = 0;
+    for (i, &p) in pattern.iter().enumerate() {
+        if p != text[start + i] {
+            mismatches += 1;
+            if mismatches > max_mismatch {
+                return None;
+            }
+        }
+    }
+
+    Some(mismatches)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,41 +404,173 @@ mod tests {
             pattern: pattern.clone(),
             k,
             min_hits,
+            max_mismatch: 0,
+            sketch_size: 0,
         };
-        let index = KmerSearch::build(config);
+        let index = KmerSearch::build(&config);
 
         let text_single = b"__ACGTACGT__";
         // The match "ACGTACGT" starts at index 2 in text_single
-        let found = KmerSearch::find_bytes(&index, text_single, &[]);
+        let found = KmerSearch::find_bytes(&config, &index, text_single);
         assert_eq!(found, Some(2));
 
         // Create text with two occurrences: index 0 and index 10
         let text_multi = b"ACGTACGT__ACGTACGT";
-        let all_found = KmerSearch::find_all_bytes(&index, text_multi, &[]);
+        let all_found = KmerSearch::find_all_bytes(&config, &index, text_multi);
         assert_eq!(all_found, vec![0, 10]);
 
         let text_none = b"ZZZZZZZZZZ";
-        let none_found = KmerSearch::find_bytes(&index, text_none, &[]);
+        let none_found = KmerSearch::find_bytes(&config, &index, text_none);
         assert_eq!(none_found, None);
     }
 
     // this test illustrates that the amount of kmers should be >= min_hits
     #[test]
     fn test_partial_hits_threshold() {
-        let pattern = b"AAAAA".to_vec();
+        let pattern = b"AAAA".to_vec();
         let config = KmerConfig {
             pattern,
             k: 2,
             min_hits: 3,
+            max_mismatch: 0,
+            sketch_size: 0,
         };
-        let index = KmerSearch::build(config);
+        let index = KmerSearch::build(&config);
 
-        // Text has 3 'A's -> 2 kmers (AA, AA). Should fail (2 < 3).
+        // Text "AAA" seeds diagonal 0 only twice (AA@0, AA@1). Should fail (2 < 3).
         let text_fail = b"AAA";
-        assert_eq!(KmerSearch::find_bytes(&index.clone(), text_fail, &[]), None);
+        assert_eq!(KmerSearch::find_bytes(&config, &index, text_fail), None);
 
-        // Text has 4 'A's -> 3 kmers (AA, AA, AA). Should pass (3 >= 3).
+        // Text "AAAA" reaches 3 seeds on diagonal 0 and verifies exactly.
         let text_pass = b"AAAA";
-        assert_eq!(KmerSearch::find_bytes(&index, text_pass, &[]), Some(0));
+        assert_eq!(KmerSearch::find_bytes(&config, &index, text_pass), Some(0));
+    }
+
+    #[test]
+    fn test_extension_rejects_false_positive() {
+        // Shared k-mers seed the diagonal, but the full alignment differs beyond
+        // the tolerance, so exact search rejects it.
+        let config = KmerConfig {
+            pattern: b"ACGTACGT".to_vec(),
+            k: 3,
+            min_hits: 1,
+            max_mismatch: 0,
+            sketch_size: 0,
+        };
+        let index = KmerSearch::build(&config);
+
+        // "ACGTTCGT" shares the "ACG"/"CGT" seeds but mismatches at index 4.
+        let text = b"__ACGTTCGT__";
+        assert_eq!(KmerSearch::find_bytes(&config, &index, text), None);
+    }
+
+    #[test]
+    fn test_approximate_match_with_mismatches() {
+        let config = KmerConfig {
+            pattern: b"ACGTACGT".to_vec(),
+            k: 3,
+            min_hits: 1,
+            max_mismatch: 1,
+            sketch_size: 0,
+        };
+        let index = KmerSearch::build(&config);
+
+        // One substitution at index 4 is within tolerance.
+        let text = b"__ACGTTCGT__";
+        assert_eq!(
+            KmerSearch::find_with_mismatches(&config, &index, text),
+            Some((2, 1))
+        );
+    }
+
+    #[test]
+    fn test_extension_clamps_to_text_bounds() {
+        // Pattern cannot fit in the shorter text even though seeds accumulate.
+        let config = KmerConfig {
+            pattern: b"ACGTACGT".to_vec(),
+            k: 3,
+            min_hits: 1,
+            max_mismatch: 0,
+            sketch_size: 0,
+        };
+        let index = KmerSearch::build(&config);
+
+        let text = b"ACGTAC";
+        assert_eq!(KmerSearch::find_bytes(&config, &index, text), None);
+    }
+
+    #[test]
+    fn test_minhash_containment_estimate() {
+        let config = KmerConfig {
+            pattern: b"ACGTACGT".to_vec(),
+            k: 3,
+            min_hits: 1,
+            max_mismatch: 0,
+            sketch_size: 16,
+        };
+        let index = KmerSearch::build(&config);
+        let pattern_sketch = index.pattern_sketch().expect("sketch requested");
+
+        // A column that embeds the pattern fully contains its k-mers.
+        let present = index.sketch_text(b"xxxxACGTACGTxxxx");
+        assert_eq!(estimate_containment(pattern_sketch, &present), 1.0);
+
+        // A disjoint column shares essentially none of them.
+        let absent = index.sketch_text(b"TTTTTTTTTTTTTTTT");
+        assert_eq!(estimate_containment(pattern_sketch, &absent), 0.0);
+    }
+
+    #[test]
+    fn test_hll_estimates_low_diversity() {
+        // A run of identical bytes has a single distinct 4-mer.
+        let text = vec![b'A'; 128];
+        let est = estimate_distinct_kmers(&text, 4);
+        assert_eq!(est, 1);
+    }
+
+    #[test]
+    fn test_hll_estimate_within_tolerance() {
+        // Distinct 4-mers of "ABCDABCDABCD..." are the 4 rotations of "ABCD".
+        let unit = b"ABCD";
+        let mut text = Vec::new();
+        for _ in 0..64 {
+            text.extend_from_slice(unit);
+        }
+        let est = estimate_distinct_kmers(&text, 4);
+        assert_eq!(est, 4);
+    }
+
+    #[test]
+    fn test_hll_merge_is_register_max() {
+        let mut a = HyperLogLog::new(HLL_PRECISION);
+        let mut b = HyperLogLog::new(HLL_PRECISION);
+        for i in 0..500u64 {
+            a.add_hash(hash_kmer(&i.to_le_bytes()));
+        }
+        for i in 300..800u64 {
+            b.add_hash(hash_kmer(&i.to_le_bytes()));
+        }
+
+        let mut merged = a.clone();
+        merged.merge(&b);
+
+        // Union of 0..800 distinct values; merged estimate covers both halves.
+        let union = merged.estimate();
+        assert!(union >= a.estimate());
+        assert!(union >= b.estimate());
+        assert!((union as i64 - 800).abs() <= 80, "estimate {} off", union);
+    }
+
+    #[test]
+    fn test_sketch_disabled_without_size() {
+        let config = KmerConfig {
+            pattern: b"ACGTACGT".to_vec(),
+            k: 3,
+            min_hits: 1,
+            max_mismatch: 0,
+            sketch_size: 0,
+        };
+        let index = KmerSearch::build(&config);
+        assert!(index.pattern_sketch().is_none());
     }
 }
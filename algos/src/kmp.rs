@@ -1,16 +1,23 @@
-use crate::StringSearch;
+use crate::{CompileOptions, StringSearch};
 
 pub struct KMP;
 
 impl StringSearch for KMP {
-    type Config = ();
+    type Config<'p> = &'p [u8];
     type State = ();
-    fn find_bytes(_state: Self::State, text: &[u8], pattern: &[u8]) -> Option<usize> {
-        kmp_find(text, pattern)
+
+    fn compile(pattern: &str, _options: CompileOptions) -> Self::Config<'_> {
+        pattern.as_bytes()
+    }
+
+    fn build(_config: &Self::Config<'_>) -> Self::State {}
+
+    fn find_bytes(config: &Self::Config<'_>, _state: &Self::State, text: &[u8]) -> Option<usize> {
+        kmp_find(text, config)
     }
 
-    fn find_all_bytes(_state: Self::State, text: &[u8], pattern: &[u8]) -> Vec<usize> {
-        kmp_find_all(text, pattern)
+    fn find_all_bytes(config: &Self::Config<'_>, _state: &Self::State, text: &[u8]) -> Vec<usize> {
+        kmp_find_all(text, config)
     }
 }
 
@@ -1,33 +1,232 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "portable-simd", feature(portable_simd))]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+mod aho_corasick;
 mod bm;
 mod kmer;
 mod kmp;
+mod lut_short;
 mod naive;
+mod prefilter;
 
 pub mod compare;
 
+/// Options controlling how a pattern literal is compiled into a backend config.
+///
+/// Lives here rather than in `like` so a backend's [`StringSearch::compile`] can
+/// honour underscore-as-wildcard semantics while building its config; `like`
+/// re-exports this type so existing `like::CompileOptions` paths keep working.
+#[derive(Debug, Clone, Copy)]
+pub struct CompileOptions {
+    pub treat_underscore_as_literal: bool,
+    pub literal_underscore_is_wildcard: bool,
+}
+
+impl Default for CompileOptions {
+    fn default() -> Self {
+        Self {
+            treat_underscore_as_literal: false,
+            literal_underscore_is_wildcard: false,
+        }
+    }
+}
+
 pub trait StringSearch {
-    type Config;
+    /// Compiled configuration for one pattern literal. The lifetime `'p` ties the
+    /// config to the pattern bytes it was built from, so borrow-checking alone
+    /// guarantees a compiled pattern cannot outlive its source — no `transmute`
+    /// laundering required at the call site.
+    type Config<'p>;
     type State;
 
-    fn build(_config: Self::Config) -> Self::State { 
-        unimplemented!("this algorithm doesnt use build");
+    /// Compile a pattern literal into this backend's config, borrowing from the
+    /// pattern for the region `'p`.
+    fn compile(pattern: &str, options: CompileOptions) -> Self::Config<'_>;
+
+    /// Build reusable search state from a compiled config.
+    fn build(config: &Self::Config<'_>) -> Self::State;
+
+    fn find_bytes(config: &Self::Config<'_>, state: &Self::State, text: &[u8]) -> Option<usize>;
+
+    /// All-matches scan, overlapping matches included. The default walks
+    /// `find_bytes` again from one byte past each match start; backends with a
+    /// dedicated routine override it.
+    fn find_all_bytes(config: &Self::Config<'_>, state: &Self::State, text: &[u8]) -> Vec<usize> {
+        let mut out = Vec::new();
+        let mut offset = 0;
+        while offset <= text.len() {
+            match Self::find_bytes(config, state, &text[offset..]) {
+                Some(pos) => {
+                    out.push(offset + pos);
+                    offset += pos + 1;
+                }
+                None => break,
+            }
+        }
+        out
+    }
+
+    fn find_str(config: &Self::Config<'_>, state: &Self::State, text: &str) -> Option<usize> {
+        Self::find_bytes(config, state, text.as_bytes())
+    }
+
+    fn find_all(config: &Self::Config<'_>, state: &Self::State, text: &str) -> Vec<usize> {
+        Self::find_all_bytes(config, state, text.as_bytes())
     }
-    fn find_bytes(state: Self::State, text: &[u8], pattern: &[u8]) -> Option<usize>;
-    fn find_all_bytes(state: Self::State, text: &[u8], pattern: &[u8]) -> Vec<usize>;
-    fn find(state: Self::State, text: &str, pattern: &str) -> Option<usize> {
-        let text_bytes = text.as_bytes();
-        let pattern_bytes = pattern.as_bytes();
-        Self::find_bytes(state, text_bytes, pattern_bytes)
+
+    /// Absolute offset of the first match of `pattern` in a streaming `reader`,
+    /// without loading the whole input into memory.
+    ///
+    /// A rolling buffer is refilled chunk by chunk; before each refill the last
+    /// `pattern.len() - 1` bytes are retained and prepended to the next chunk so a
+    /// match straddling a chunk boundary is not missed. Emitted offsets are
+    /// absolute (relative to the start of the stream). Provided on the trait, so
+    /// every backend gets streaming for free. Requires the `std` feature.
+    #[cfg(feature = "std")]
+    fn find_in_reader<R: std::io::Read>(
+        reader: R,
+        pattern: &str,
+        options: CompileOptions,
+    ) -> std::io::Result<Option<u64>> {
+        if pattern.is_empty() {
+            return Ok(Some(0));
+        }
+        let config = Self::compile(pattern, options);
+        let state = Self::build(&config);
+        let overlap = pattern.len() - 1;
+
+        let mut reader = reader;
+        let mut chunk = [0u8; STREAM_CHUNK];
+        let mut buf: Vec<u8> = Vec::new();
+        let mut base: u64 = 0;
+
+        loop {
+            let got = reader.read(&mut chunk)?;
+            if got == 0 {
+                for pos in Self::find_all_bytes(&config, &state, &buf) {
+                    return Ok(Some(base + pos as u64));
+                }
+                return Ok(None);
+            }
+            buf.extend_from_slice(&chunk[..got]);
+            if buf.len() > overlap {
+                let process_upto = buf.len() - overlap;
+                for pos in Self::find_all_bytes(&config, &state, &buf) {
+                    if pos < process_upto {
+                        return Ok(Some(base + pos as u64));
+                    }
+                }
+                buf.drain(..process_upto);
+                base += process_upto as u64;
+            }
+        }
+    }
+
+    /// Absolute offsets of every match of `pattern` in a streaming `reader`. Like
+    /// [`Self::find_in_reader`] but collects all matches; see it for the overlap
+    /// mechanics. Requires the `std` feature.
+    #[cfg(feature = "std")]
+    fn find_all_in_reader<R: std::io::Read>(
+        reader: R,
+        pattern: &str,
+        options: CompileOptions,
+    ) -> std::io::Result<Vec<u64>> {
+        let mut out = Vec::new();
+        if pattern.is_empty() {
+            return Ok(out);
+        }
+        let config = Self::compile(pattern, options);
+        let state = Self::build(&config);
+        let overlap = pattern.len() - 1;
+
+        let mut reader = reader;
+        let mut chunk = [0u8; STREAM_CHUNK];
+        let mut buf: Vec<u8> = Vec::new();
+        let mut base: u64 = 0;
+
+        loop {
+            let got = reader.read(&mut chunk)?;
+            if got == 0 {
+                for pos in Self::find_all_bytes(&config, &state, &buf) {
+                    out.push(base + pos as u64);
+                }
+                return Ok(out);
+            }
+            buf.extend_from_slice(&chunk[..got]);
+            if buf.len() > overlap {
+                let process_upto = buf.len() - overlap;
+                for pos in Self::find_all_bytes(&config, &state, &buf) {
+                    if pos < process_upto {
+                        out.push(base + pos as u64);
+                    }
+                }
+                buf.drain(..process_upto);
+                base += process_upto as u64;
+            }
+        }
     }
-    fn find_all(state: Self::State, text: &str, pattern: &str) -> Vec<usize> {
-        let text_bytes = text.as_bytes();
-        let pattern_bytes = pattern.as_bytes();
-        Self::find_all_bytes(state, text_bytes, pattern_bytes)
+}
+
+/// Chunk size for the streaming reader search buffer.
+#[cfg(feature = "std")]
+const STREAM_CHUNK: usize = 64 * 1024;
+
+#[cfg(all(test, feature = "std"))]
+mod stream_tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn finds_match_across_chunk_boundary() {
+        // A reader whose `read` hands back one byte at a time forces the pattern
+        // to straddle buffer refills, exercising the retained-overlap logic.
+        struct DripReader<'a> {
+            data: &'a [u8],
+            pos: usize,
+        }
+        impl std::io::Read for DripReader<'_> {
+            fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+                if self.pos >= self.data.len() || out.is_empty() {
+                    return Ok(0);
+                }
+                out[0] = self.data[self.pos];
+                self.pos += 1;
+                Ok(1)
+            }
+        }
+
+        let text = b"the quick brown fox";
+        let reader = DripReader { data: text, pos: 0 };
+        let first =
+            Naive::find_in_reader(reader, "brown", CompileOptions::default()).unwrap();
+        assert_eq!(first, Some(10));
+
+        let reader = DripReader { data: text, pos: 0 };
+        let all =
+            Naive::find_all_in_reader(reader, "o", CompileOptions::default()).unwrap();
+        assert_eq!(all, vec![12, 17]);
+    }
+
+    #[test]
+    fn no_match_returns_none() {
+        let none = Naive::find_in_reader(Cursor::new(b"abcabc"), "xyz", CompileOptions::default())
+            .unwrap();
+        assert_eq!(none, None);
     }
 }
 
+pub use aho_corasick::{AhoCorasick, AhoCorasickSearch, Hit};
+pub use lut_short::{ByteClass, LutShort, LutShortBuilder, LutShortState};
+pub use prefilter::{find_all_with as prefilter_find_all, AUTO_PREFILTER_MIN_LEN};
 pub use naive::{Naive, NaiveScalar, NaiveVectorized};
 pub use kmp::KMP;
-pub use bm::BM;
-pub use kmer::{KmerIndex, KmerConfig, KmerSearch};
+pub use bm::{BmState, BM};
+pub use kmer::{
+    estimate_containment, estimate_distinct_kmers, HyperLogLog, KmerConfig, KmerIndex, KmerSearch,
+    Sketch,
+};
 
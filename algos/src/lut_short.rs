@@ -1,5 +1,5 @@
 #![allow(dead_code, unused_variables)]
-use crate::StringSearch;
+use crate::{CompileOptions, StringSearch};
 
 pub struct LutShort<'a>(std::marker::PhantomData<&'a ()>);
 
@@ -11,40 +11,300 @@ pub struct LutShortState {
     sig_index: usize,
     lut_lo: [u8; 16],
     lut_hi: [u8; 16],
+    // Second signature prefilter: when `two_sig` is set, candidates must also
+    // carry the rarer second byte `delta` lanes ahead of the first signature.
+    two_sig: bool,
+    delta: usize,
+    lut_lo2: [u8; 16],
+    lut_hi2: [u8; 16],
+    // Per-position acceptable-byte classes. `None` is the plain literal case
+    // (verification compares against the raw pattern); `Some` carries one class
+    // per position for case-insensitive or character-class matching.
+    classes: Option<Vec<ByteClass>>,
+}
+
+/// The set of bytes accepted at a single pattern position, stored as a 256-bit
+/// membership bitmap. This keeps the prefilter a pure set-membership test while
+/// letting a position stand for more than one literal byte (either case, a
+/// digit, whitespace, ...) without pulling in a full regex engine.
+#[derive(Clone, Debug)]
+pub struct ByteClass {
+    bits: [u64; 4],
+}
+
+impl ByteClass {
+    /// A class matching exactly one byte.
+    pub fn literal(byte: u8) -> Self {
+        let mut class = ByteClass { bits: [0; 4] };
+        class.insert(byte);
+        class
+    }
+
+    /// A class matching both ASCII cases of `byte` (a no-op for non-letters).
+    pub fn either_case(byte: u8) -> Self {
+        let mut class = ByteClass { bits: [0; 4] };
+        class.insert(byte.to_ascii_lowercase());
+        class.insert(byte.to_ascii_uppercase());
+        class
+    }
+
+    /// The ASCII decimal digits `0`–`9`.
+    pub fn digit() -> Self {
+        let mut class = ByteClass { bits: [0; 4] };
+        for b in b'0'..=b'9' {
+            class.insert(b);
+        }
+        class
+    }
+
+    /// ASCII whitespace (space, tab, newline, carriage return, form feed).
+    pub fn whitespace() -> Self {
+        let mut class = ByteClass { bits: [0; 4] };
+        for &b in &[b' ', b'\t', b'\n', b'\r', 0x0c] {
+            class.insert(b);
+        }
+        class
+    }
+
+    fn insert(&mut self, byte: u8) {
+        self.bits[(byte >> 6) as usize] |= 1u64 << (byte & 0x3f);
+    }
+
+    /// Whether `byte` is a member of the class.
+    pub fn contains(&self, byte: u8) -> bool {
+        self.bits[(byte >> 6) as usize] & (1u64 << (byte & 0x3f)) != 0
+    }
+
+    /// Number of distinct bytes in the class.
+    fn len(&self) -> u32 {
+        self.bits.iter().map(|w| w.count_ones()).sum()
+    }
+
+    /// Fold in the opposite ASCII case of every current member, turning a
+    /// literal or class into its case-insensitive counterpart.
+    fn make_case_insensitive(&mut self) {
+        for b in 0u8..=255 {
+            if self.contains(b) {
+                self.insert(b.to_ascii_lowercase());
+                self.insert(b.to_ascii_uppercase());
+            }
+        }
+    }
+
+    /// Visit each member byte in ascending order.
+    fn for_each<F: FnMut(u8)>(&self, mut f: F) {
+        for b in 0u8..=255 {
+            if self.contains(b) {
+                f(b);
+            }
+        }
+    }
+}
+
+/// Builder for a [`LutShortState`] with optional case folding or per-position
+/// byte classes. Use this instead of the bare `&[u8]` config when the match
+/// should accept more than the literal pattern bytes.
+pub struct LutShortBuilder {
+    classes: Vec<ByteClass>,
+}
+
+impl LutShortBuilder {
+    /// Start from a literal pattern: each position accepts exactly its byte.
+    pub fn new(pattern: &[u8]) -> Self {
+        LutShortBuilder {
+            classes: pattern.iter().map(|&b| ByteClass::literal(b)).collect(),
+        }
+    }
+
+    /// Fold ASCII case across every position, matching regardless of case.
+    pub fn ignore_case(mut self) -> Self {
+        for class in &mut self.classes {
+            class.make_case_insensitive();
+        }
+        self
+    }
+
+    /// Replace the class at `index` with an explicit one. Out-of-range indices
+    /// are ignored so the call chains cleanly.
+    pub fn class(mut self, index: usize, class: ByteClass) -> Self {
+        if let Some(slot) = self.classes.get_mut(index) {
+            *slot = class;
+        }
+        self
+    }
+
+    /// Build the search state against the default background ranks.
+    pub fn build(self) -> LutShortState {
+        build_state_from_classes(self.classes, &DEFAULT_RANKS)
+    }
 }
 
 impl<'a> StringSearch for LutShort<'a> {
-    type Config = &'a [u8];
+    type Config<'p> = &'p [u8];
     type State = LutShortState;
 
-    fn build(config: &Self::Config) -> Self::State {
+    fn compile(pattern: &str, _options: CompileOptions) -> Self::Config<'_> {
+        pattern.as_bytes()
+    }
+
+    fn build(config: &Self::Config<'_>) -> Self::State {
         build_state(config)
     }
 
-    fn find_bytes(config: &Self::Config, state: &Self::State, text: &[u8]) -> Option<usize> {
-        #[cfg(all(target_arch = "x86_64", target_feature = "ssse3"))]
-        unsafe {
-            return x86::find_ssse3(state, text, config);
+    fn find_bytes(config: &Self::Config<'_>, state: &Self::State, text: &[u8]) -> Option<usize> {
+        find_dispatch(state, text, config)
+    }
+}
+
+/// Pick the SIMD backend at *runtime* rather than at compile time, so a binary
+/// built for a generic baseline still uses SSSE3/NEON when the host supports it
+/// and otherwise falls back to the scalar search. The `is_*_feature_detected!`
+/// macros cache their probe, so this stays cheap on repeated calls.
+fn find_dispatch(state: &LutShortState, text: &[u8], pattern: &[u8]) -> Option<usize> {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::is_x86_feature_detected!("ssse3") {
+            // Safety: guarded by the runtime SSSE3 probe above.
+            return unsafe { x86::find_ssse3(state, text, pattern) };
         }
+    }
 
-        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
-        unsafe {
-            return neon::find_neon(state, text, config);
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            // Safety: guarded by the runtime NEON probe above.
+            return unsafe { neon::find_neon(state, text, pattern) };
+        }
+    }
+
+    scalar_find(state, text, pattern)
+}
+
+impl<'a> LutShort<'a> {
+    /// Build a state choosing the signature byte against a caller-supplied
+    /// background rank table. Useful for binary or DNA-like data where the
+    /// default ASCII frequencies do not apply.
+    pub fn build_with_ranks(pattern: &'a [u8], ranks: &[u16; 256]) -> LutShortState {
+        build_state_with_ranks(pattern, ranks)
+    }
+
+    /// Iterate over every match of `pattern` in `text`, reusing the LUTs held by
+    /// `state` across yields instead of rebuilding them per call. Set
+    /// `overlapping` to report overlapping matches (advance by one byte) or
+    /// clear it to skip past each match (advance by the pattern length).
+    pub fn find_iter<'t>(
+        config: &'t &'a [u8],
+        state: &'t LutShortState,
+        text: &'t [u8],
+        overlapping: bool,
+    ) -> LutShortIter<'t> {
+        LutShortIter {
+            pattern: config,
+            state,
+            text,
+            pos: 0,
+            overlapping,
+        }
+    }
+
+    /// Find the *last* occurrence of `pattern` in `text`, testing alignments one
+    /// at a time from the end (a scalar loop, with no SIMD block scan) and
+    /// verifying candidates against the full pattern.
+    pub fn rfind_bytes(config: &&'a [u8], state: &LutShortState, text: &[u8]) -> Option<usize> {
+        let pattern = *config;
+        let m = state.len;
+        let n = text.len();
+        if m == 0 {
+            return Some(n);
+        }
+        if m > n {
+            return None;
         }
 
-        #[cfg(not(any(
-            all(target_arch = "x86_64", target_feature = "ssse3"),
-            all(target_arch = "aarch64", target_feature = "neon")
-        )))]
-        {
-            let _ = state;
-            let _ = text;
-            unimplemented!("lut-short requires SSSE3 or NEON");
+        // Walk the valid alignment range [0, n - m] in reverse, keying on the
+        // rarest byte position just like the forward scan.
+        let sig = state.sig;
+        let sig_index = state.sig_index;
+        let mut start = n - m + 1;
+        while start > 0 {
+            start -= 1;
+            let cand = start + sig_index;
+            // In class mode the signature position accepts a whole set, so the
+            // cheap byte-equality prefilter only applies to literal patterns.
+            let sig_hit = match &state.classes {
+                Some(classes) => classes[sig_index].contains(text[cand]),
+                None => text[cand] == sig,
+            };
+            if sig_hit && verify_match(state, text, start, pattern) {
+                return Some(start);
+            }
         }
+        None
     }
 }
 
+/// Iterator over matches produced by [`LutShort::find_iter`].
+pub struct LutShortIter<'a> {
+    pattern: &'a [u8],
+    state: &'a LutShortState,
+    text: &'a [u8],
+    pos: usize,
+    overlapping: bool,
+}
+
+impl<'a> Iterator for LutShortIter<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let m = self.state.len.max(1);
+        while self.pos <= self.text.len() {
+            match find_dispatch(self.state, &self.text[self.pos..], self.pattern) {
+                Some(rel) => {
+                    let abs = self.pos + rel;
+                    self.pos = if self.overlapping { abs + 1 } else { abs + m };
+                    return Some(abs);
+                }
+                None => return None,
+            }
+        }
+        None
+    }
+}
+
+/// Background byte-frequency ranks. A higher value means the byte is *more*
+/// common in typical mixed ASCII/UTF-8 text, so the rarest signature byte is
+/// the pattern position with the lowest rank. Bytes absent from the table keep
+/// the floor rank of 1 (treated as rare).
+pub static DEFAULT_RANKS: [u16; 256] = build_default_ranks();
+
+const fn build_default_ranks() -> [u16; 256] {
+    let mut table = [1u16; 256];
+    table[b' ' as usize] = 1000;
+    table[b'e' as usize] = 900;
+    table[b't' as usize] = 800;
+    table[b'a' as usize] = 750;
+    table[b'o' as usize] = 700;
+    table[b'i' as usize] = 680;
+    table[b'n' as usize] = 670;
+    table[b's' as usize] = 660;
+    table[b'r' as usize] = 650;
+    table[b'h' as usize] = 620;
+    table[b'l' as usize] = 500;
+    table[b'd' as usize] = 450;
+    table[b'\n' as usize] = 400;
+    // Nucleotides are extremely common in the DNA benchmark inputs.
+    table[b'A' as usize] = 950;
+    table[b'C' as usize] = 950;
+    table[b'G' as usize] = 950;
+    table[b'T' as usize] = 950;
+    table
+}
+
 fn build_state(pattern: &[u8]) -> LutShortState {
+    build_state_with_ranks(pattern, &DEFAULT_RANKS)
+}
+
+fn build_state_with_ranks(pattern: &[u8], ranks: &[u16; 256]) -> LutShortState {
     let len = pattern.len();
     let mut buf = [0u8; 8];
     if len > 0 {
@@ -52,13 +312,30 @@ fn build_state(pattern: &[u8]) -> LutShortState {
         buf[..copy_len].copy_from_slice(&pattern[..copy_len]);
     }
 
-    let (sig, sig_index) = rarest_byte(pattern);
+    let (mut sig, mut sig_index) = rarest_byte(pattern, ranks);
     let mut lut_lo = [0u8; 16];
     let mut lut_hi = [0u8; 16];
-    let lo = (sig & 0x0f) as usize;
-    let hi = (sig >> 4) as usize;
-    lut_lo[lo] = 0xff;
-    lut_hi[hi] = 0xff;
+    let mut two_sig = false;
+    let mut delta = 0usize;
+    let mut lut_lo2 = [0u8; 16];
+    let mut lut_hi2 = [0u8; 16];
+
+    // For patterns with at least two bytes, add a second signature at the next
+    // rarest position so only windows carrying *both* rare bytes, at the right
+    // relative offset, become candidates. The primary signature is always the
+    // earlier of the two positions so the second is found at a forward shift.
+    if len >= 2 {
+        if let Some((_, sig2_index)) = second_rarest(pattern, sig_index, ranks) {
+            let i0 = sig_index.min(sig2_index);
+            let i1 = sig_index.max(sig2_index);
+            sig = pattern[i0];
+            sig_index = i0;
+            mark_byte(&mut lut_lo2, &mut lut_hi2, pattern[i1]);
+            two_sig = true;
+            delta = i1 - i0;
+        }
+    }
+    mark_byte(&mut lut_lo, &mut lut_hi, sig);
 
     LutShortState {
         pattern: buf,
@@ -67,49 +344,215 @@ fn build_state(pattern: &[u8]) -> LutShortState {
         sig_index,
         lut_lo,
         lut_hi,
+        two_sig,
+        delta,
+        lut_lo2,
+        lut_hi2,
+        classes: None,
     }
 }
 
-fn rarest_byte(pattern: &[u8]) -> (u8, usize) {
-    if pattern.is_empty() {
-        return (0, 0);
+/// Build a state from explicit per-position byte classes. Unlike the literal
+/// path this keeps the full class set for verification and marks *every* byte
+/// of the most selective position into the prefilter LUT, so the single
+/// signature still narrows candidates while the verifier honours the classes.
+fn build_state_from_classes(classes: Vec<ByteClass>, ranks: &[u16; 256]) -> LutShortState {
+    let len = classes.len();
+    let mut buf = [0u8; 8];
+    let mut lut_lo = [0u8; 16];
+    let mut lut_hi = [0u8; 16];
+
+    if len == 0 {
+        return LutShortState {
+            pattern: buf,
+            len,
+            sig: 0,
+            sig_index: 0,
+            lut_lo,
+            lut_hi,
+            two_sig: false,
+            delta: 0,
+            lut_lo2: [0u8; 16],
+            lut_hi2: [0u8; 16],
+            classes: Some(classes),
+        };
     }
 
-    let mut counts = [0u8; 256];
-    for &b in pattern {
-        counts[b as usize] = counts[b as usize].saturating_add(1);
+    // Pick the most selective position: the smallest class, breaking ties
+    // toward the rarest representative byte in typical text.
+    let sig_index = most_selective_class(&classes, ranks);
+    let sig_class = &classes[sig_index];
+    sig_class.for_each(|b| mark_byte(&mut lut_lo, &mut lut_hi, b));
+
+    // A representative literal byte, used only for the scalar tail fill.
+    let mut sig = 0u8;
+    sig_class.for_each(|b| {
+        if sig == 0 {
+            sig = b;
+        }
+    });
+
+    // Capture a literal "shadow" of the pattern for the inline buffer: the
+    // first member of each class. Verification uses the classes, not this.
+    let copy_len = len.min(8);
+    for (i, slot) in buf[..copy_len].iter_mut().enumerate() {
+        classes[i].for_each(|b| {
+            if *slot == 0 {
+                *slot = b;
+            }
+        });
+    }
+
+    LutShortState {
+        pattern: buf,
+        len,
+        sig,
+        sig_index,
+        lut_lo,
+        lut_hi,
+        two_sig: false,
+        delta: 0,
+        lut_lo2: [0u8; 16],
+        lut_hi2: [0u8; 16],
+        classes: Some(classes),
+    }
+}
+
+/// Index of the smallest (most selective) class, ties broken toward the rarest
+/// representative byte so the prefilter keys on the least common position.
+fn most_selective_class(classes: &[ByteClass], ranks: &[u16; 256]) -> usize {
+    let mut best_idx = 0usize;
+    let mut best_len = u32::MAX;
+    let mut best_rank = u16::MAX;
+    for (idx, class) in classes.iter().enumerate() {
+        let len = class.len();
+        let mut rank = 0u16;
+        class.for_each(|b| rank = rank.max(ranks[b as usize]));
+        if len < best_len || (len == best_len && rank < best_rank) {
+            best_idx = idx;
+            best_len = len;
+            best_rank = rank;
+        }
+    }
+    best_idx
+}
+
+/// Set the nibble-membership slots for `byte` in a LUT pair.
+fn mark_byte(lut_lo: &mut [u8; 16], lut_hi: &mut [u8; 16], byte: u8) {
+    lut_lo[(byte & 0x0f) as usize] = 0xff;
+    lut_hi[(byte >> 4) as usize] = 0xff;
+}
+
+/// Pick the second-rarest pattern position distinct from `first_index`.
+fn second_rarest(pattern: &[u8], first_index: usize, ranks: &[u16; 256]) -> Option<(u8, usize)> {
+    let mut best: Option<(u8, usize, u16)> = None;
+    for (idx, &b) in pattern.iter().enumerate() {
+        if idx == first_index {
+            continue;
+        }
+        let rank = ranks[b as usize];
+        match best {
+            Some((_, _, best_rank)) if rank > best_rank => {}
+            _ => best = Some((b, idx, rank)),
+        }
+    }
+    best.map(|(b, idx, _)| (b, idx))
+}
+
+/// Pick the pattern position whose byte is rarest in typical text according to
+/// `ranks`. Ties are broken toward positions farther from the start, which
+/// tends to spread candidate hits out and verify fewer false positives.
+fn rarest_byte(pattern: &[u8], ranks: &[u16; 256]) -> (u8, usize) {
+    if pattern.is_empty() {
+        return (0, 0);
     }
 
     let mut best = pattern[0];
     let mut best_idx = 0usize;
-    let mut best_count = counts[best as usize];
+    let mut best_rank = ranks[best as usize];
 
     for (idx, &b) in pattern.iter().enumerate() {
-        let count = counts[b as usize];
-        if count < best_count {
+        let rank = ranks[b as usize];
+        // `<=` breaks ties toward the later (farther) position.
+        if rank <= best_rank {
             best = b;
             best_idx = idx;
-            best_count = count;
+            best_rank = rank;
         }
     }
 
     (best, best_idx)
 }
 
-fn matches_at(state: &LutShortState, text: &[u8], pos: usize) -> bool {
+/// Confirm a candidate by comparing the full pattern slice against the text.
+/// The prefilter only keys on one or two signature bytes, so the length is no
+/// longer bounded by the inline `LutShortState::pattern` buffer.
+fn matches_at(text: &[u8], pos: usize, pattern: &[u8]) -> bool {
+    let m = pattern.len();
+    pos + m <= text.len() && &text[pos..pos + m] == pattern
+}
+
+/// Confirm a candidate honouring the state's byte classes when present, falling
+/// back to a plain literal comparison otherwise.
+fn verify_match(state: &LutShortState, text: &[u8], pos: usize, pattern: &[u8]) -> bool {
+    match &state.classes {
+        Some(classes) => {
+            let m = classes.len();
+            pos + m <= text.len()
+                && text[pos..pos + m]
+                    .iter()
+                    .zip(classes)
+                    .all(|(&b, class)| class.contains(b))
+        }
+        None => matches_at(text, pos, pattern),
+    }
+}
+
+/// Class-aware scalar search, used as the fallback when no SIMD backend is
+/// available on the host.
+fn scalar_find(state: &LutShortState, text: &[u8], pattern: &[u8]) -> Option<usize> {
     let m = state.len;
-    if pos + m > text.len() {
-        return false;
+    let n = text.len();
+    if m == 0 {
+        return Some(0);
     }
-    let pat = &state.pattern[..m];
-    &text[pos..pos + m] == pat
+    if m > n {
+        return None;
+    }
+    if state.classes.is_none() {
+        return crate::naive::naive_find_scalar(text, pattern);
+    }
+    (0..=n - m).find(|&start| verify_match(state, text, start, pattern))
 }
 
-#[cfg(all(target_arch = "x86_64", target_feature = "ssse3"))]
+#[cfg(target_arch = "x86_64")]
 mod x86 {
-    use super::{matches_at, LutShortState};
+    use super::{verify_match, LutShortState};
     use core::arch::x86_64::*;
 
+    /// Movemask for the second signature over the already-split nibbles.
+    #[target_feature(enable = "ssse3,sse2")]
+    unsafe fn sig2_movemask(state: &LutShortState, lo: __m128i, hi: __m128i) -> u32 {
+        let lut_lo2 = unsafe { _mm_loadu_si128(state.lut_lo2.as_ptr() as *const __m128i) };
+        let lut_hi2 = unsafe { _mm_loadu_si128(state.lut_hi2.as_ptr() as *const __m128i) };
+        let lo_mask = _mm_shuffle_epi8(lut_lo2, lo);
+        let hi_mask = _mm_shuffle_epi8(lut_hi2, hi);
+        _mm_movemask_epi8(_mm_and_si128(lo_mask, hi_mask)) as u32
+    }
+
+    /// Combine the first and second signature masks: a lane is a candidate only
+    /// if the first signature sits there and the second sits `delta` lanes
+    /// ahead. The top `delta` lanes cannot confirm the partner within this
+    /// 16-byte window, so they fall back to the single-signature mask.
+    fn combine_two_sig(mask: u32, mask2: u32, delta: u32) -> u32 {
+        let high_lanes = if delta >= 16 {
+            0xFFFF
+        } else {
+            (!((1u32 << (16 - delta)) - 1)) & 0xFFFF
+        };
+        mask & ((mask2 >> delta) | high_lanes)
+    }
+
     #[target_feature(enable = "ssse3,sse2")]
     pub unsafe fn find_ssse3(state: &LutShortState, text: &[u8], pattern: &[u8]) -> Option<usize> {
         let m = state.len;
@@ -117,14 +560,11 @@ mod x86 {
         if m == 0 {
             return Some(0);
         }
-        if m > 8 {
-            return crate::naive::naive_find_scalar(text, pattern);
-        }
         if m > n {
             return None;
         }
-        if m == 1 {
-            let target = state.pattern[0];
+        if m == 1 && state.classes.is_none() {
+            let target = pattern[0];
             for (i, &b) in text.iter().enumerate() {
                 if b == target {
                     return Some(i);
@@ -132,6 +572,9 @@ mod x86 {
             }
             return None;
         }
+        if m == 1 {
+            return (0..n).find(|&i| verify_match(state, text, i, pattern));
+        }
 
         let lut_lo = unsafe { _mm_loadu_si128(state.lut_lo.as_ptr() as *const __m128i) };
         let lut_hi = unsafe { _mm_loadu_si128(state.lut_hi.as_ptr() as *const __m128i) };
@@ -150,6 +593,10 @@ mod x86 {
                 let hi_mask = _mm_shuffle_epi8(lut_hi, hi);
                 let eq = _mm_and_si128(lo_mask, hi_mask);
                 let mut mask = _mm_movemask_epi8(eq) as u32;
+                if state.two_sig {
+                    let mask2 = unsafe { sig2_movemask(state, lo, hi) };
+                    mask = combine_two_sig(mask, mask2, state.delta as u32);
+                }
 
                 while mask != 0 {
                     let bit = mask.trailing_zeros() as usize;
@@ -159,7 +606,7 @@ mod x86 {
                         continue;
                     }
                     let start = cand - sig_index;
-                    if start + m <= n && matches_at(state, text, start) {
+                    if start + m <= n && verify_match(state, text, start, pattern) {
                         return Some(start);
                     }
                 }
@@ -168,7 +615,7 @@ mod x86 {
         }
 
         while i + 16 <= n {
-            if let Some(pos) = unsafe { scan_block(state, text, i, 16) } {
+            if let Some(pos) = unsafe { scan_block(state, text, i, 16, pattern) } {
                 return Some(pos);
             }
             i += 16;
@@ -176,7 +623,7 @@ mod x86 {
 
         if i < n {
             let rem = n - i;
-            if let Some(pos) = unsafe { scan_tail(state, text, i, rem) } {
+            if let Some(pos) = unsafe { scan_tail(state, text, i, rem, pattern) } {
                 return Some(pos);
             }
         }
@@ -190,6 +637,7 @@ mod x86 {
         text: &[u8],
         base: usize,
         limit: usize,
+        pattern: &[u8],
     ) -> Option<usize> {
         let m = state.len;
         let n = text.len();
@@ -206,6 +654,10 @@ mod x86 {
         let hi_mask = _mm_shuffle_epi8(lut_hi, hi);
         let eq = _mm_and_si128(lo_mask, hi_mask);
         let mut mask = _mm_movemask_epi8(eq) as u32;
+        if state.two_sig {
+            let mask2 = unsafe { sig2_movemask(state, lo, hi) };
+            mask = combine_two_sig(mask, mask2, state.delta as u32);
+        }
         if limit < 16 {
             let limit_mask = if limit == 0 { 0 } else { (1u32 << limit) - 1 };
             mask &= limit_mask;
@@ -219,7 +671,7 @@ mod x86 {
                 continue;
             }
             let start = cand - sig_index;
-            if start + m <= n && matches_at(state, text, start) {
+            if start + m <= n && verify_match(state, text, start, pattern) {
                 return Some(start);
             }
         }
@@ -233,6 +685,7 @@ mod x86 {
         text: &[u8],
         base: usize,
         rem: usize,
+        pattern: &[u8],
     ) -> Option<usize> {
         let fill = state.sig.wrapping_add(1);
         let mut tmp = [fill; 16];
@@ -252,6 +705,10 @@ mod x86 {
         let hi_mask = _mm_shuffle_epi8(lut_hi, hi);
         let eq = _mm_and_si128(lo_mask, hi_mask);
         let mut mask = _mm_movemask_epi8(eq) as u32;
+        if state.two_sig {
+            let mask2 = unsafe { sig2_movemask(state, lo, hi) };
+            mask = combine_two_sig(mask, mask2, state.delta as u32);
+        }
         let limit_mask = if rem == 0 { 0 } else { (1u32 << rem) - 1 };
         mask &= limit_mask;
 
@@ -263,7 +720,7 @@ mod x86 {
                 continue;
             }
             let start = cand - sig_index;
-            if start + m <= n && matches_at(state, text, start) {
+            if start + m <= n && verify_match(state, text, start, pattern) {
                 return Some(start);
             }
         }
@@ -272,9 +729,9 @@ mod x86 {
     }
 }
 
-#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+#[cfg(target_arch = "aarch64")]
 mod neon {
-    use super::{matches_at, LutShortState};
+    use super::{verify_match, LutShortState};
     use core::arch::aarch64::*;
 
     #[target_feature(enable = "neon")]
@@ -284,14 +741,11 @@ mod neon {
         if m == 0 {
             return Some(0);
         }
-        if m > 8 {
-            return crate::naive::naive_find_scalar(text, pattern);
-        }
         if m > n {
             return None;
         }
-        if m == 1 {
-            let target = state.pattern[0];
+        if m == 1 && state.classes.is_none() {
+            let target = pattern[0];
             for (i, &b) in text.iter().enumerate() {
                 if b == target {
                     return Some(i);
@@ -299,6 +753,9 @@ mod neon {
             }
             return None;
         }
+        if m == 1 {
+            return (0..n).find(|&i| verify_match(state, text, i, pattern));
+        }
 
         let lut_lo = unsafe { vld1q_u8(state.lut_lo.as_ptr()) };
         let lut_hi = unsafe { vld1q_u8(state.lut_hi.as_ptr()) };
@@ -310,7 +767,7 @@ mod neon {
             for block in 0..4 {
                 let base = i + block * 16;
                 if let Some(pos) =
-                    unsafe { scan_block(state, text, base, 16, lut_lo, lut_hi, mask_0f) }
+                    unsafe { scan_block(state, text, base, 16, lut_lo, lut_hi, mask_0f, pattern) }
                 {
                     return Some(pos);
                 }
@@ -319,7 +776,9 @@ mod neon {
         }
 
         while i + 16 <= n {
-            if let Some(pos) = unsafe { scan_block(state, text, i, 16, lut_lo, lut_hi, mask_0f) } {
+            if let Some(pos) =
+                unsafe { scan_block(state, text, i, 16, lut_lo, lut_hi, mask_0f, pattern) }
+            {
                 return Some(pos);
             }
             i += 16;
@@ -332,7 +791,7 @@ mod neon {
             tmp[..rem].copy_from_slice(&text[i..i + rem]);
             let chunk = unsafe { vld1q_u8(tmp.as_ptr()) };
             if let Some(pos) =
-                unsafe { scan_chunk(state, text, i, rem, chunk, lut_lo, lut_hi, mask_0f) }
+                unsafe { scan_chunk(state, text, i, rem, chunk, lut_lo, lut_hi, mask_0f, pattern) }
             {
                 return Some(pos);
             }
@@ -342,6 +801,7 @@ mod neon {
     }
 
     #[target_feature(enable = "neon")]
+    #[allow(clippy::too_many_arguments)]
     unsafe fn scan_block(
         state: &LutShortState,
         text: &[u8],
@@ -350,13 +810,15 @@ mod neon {
         lut_lo: uint8x16_t,
         lut_hi: uint8x16_t,
         mask_0f: uint8x16_t,
+        pattern: &[u8],
     ) -> Option<usize> {
         let ptr = unsafe { text.as_ptr().add(base) };
         let chunk = unsafe { vld1q_u8(ptr) };
-        unsafe { scan_chunk(state, text, base, limit, chunk, lut_lo, lut_hi, mask_0f) }
+        unsafe { scan_chunk(state, text, base, limit, chunk, lut_lo, lut_hi, mask_0f, pattern) }
     }
 
     #[target_feature(enable = "neon")]
+    #[allow(clippy::too_many_arguments)]
     unsafe fn scan_chunk(
         state: &LutShortState,
         text: &[u8],
@@ -366,6 +828,7 @@ mod neon {
         lut_lo: uint8x16_t,
         lut_hi: uint8x16_t,
         mask_0f: uint8x16_t,
+        pattern: &[u8],
     ) -> Option<usize> {
         let m = state.len;
         let n = text.len();
@@ -380,16 +843,36 @@ mod neon {
         let mut lanes = [0u8; 16];
         unsafe { vst1q_u8(lanes.as_mut_ptr(), eq) };
 
+        // Second signature lanes (if configured), keyed on the same chunk.
+        let mut lanes2 = [0u8; 16];
+        if state.two_sig {
+            let lut_lo2 = unsafe { vld1q_u8(state.lut_lo2.as_ptr()) };
+            let lut_hi2 = unsafe { vld1q_u8(state.lut_hi2.as_ptr()) };
+            let lo2 = unsafe { vqtbl1q_u8(lut_lo2, lo) };
+            let hi2 = unsafe { vqtbl1q_u8(lut_hi2, hi) };
+            let eq2 = unsafe { vandq_u8(lo2, hi2) };
+            unsafe { vst1q_u8(lanes2.as_mut_ptr(), eq2) };
+        }
+
         for lane in 0..limit {
-            if lanes[lane] == 0xff {
-                let cand = base + lane;
-                if cand < sig_index {
+            if lanes[lane] != 0xff {
+                continue;
+            }
+            // Require the second signature `delta` lanes ahead when it lands
+            // inside this window; trailing lanes fall back to single signature.
+            if state.two_sig {
+                let partner = lane + state.delta;
+                if partner < limit && lanes2[partner] != 0xff {
                     continue;
                 }
-                let start = cand - sig_index;
-                if start + m <= n && matches_at(state, text, start) {
-                    return Some(start);
-                }
+            }
+            let cand = base + lane;
+            if cand < sig_index {
+                continue;
+            }
+            let start = cand - sig_index;
+            if start + m <= n && verify_match(state, text, start, pattern) {
+                return Some(start);
             }
         }
 
@@ -397,17 +880,38 @@ mod neon {
     }
 }
 
-#[cfg(all(
-    test,
-    any(
-        all(target_arch = "x86_64", target_feature = "ssse3"),
-        all(target_arch = "aarch64", target_feature = "neon")
-    )
-))]
+#[cfg(test)]
 mod tests {
-    use super::LutShort;
+    use super::{ByteClass, LutShort, LutShortBuilder};
     use crate::StringSearch;
 
+    #[test]
+    fn test_ignore_case() {
+        let text = b"The Quick BROWN fox";
+        let pat = b"brown";
+        let state = LutShortBuilder::new(pat).ignore_case().build();
+        let config = &pat[..];
+        let found = LutShort::find_bytes(&config, &state, text);
+        assert_eq!(found, Some(10));
+
+        // A case-sensitive build must not find the upper-cased run.
+        let plain = LutShort::build(&config);
+        assert_eq!(LutShort::find_bytes(&config, &plain, text), None);
+    }
+
+    #[test]
+    fn test_byte_class_digit() {
+        // Match "v" followed by a digit then "x".
+        let text = b"va1 vb2 v9x end";
+        let pat = b"v0x";
+        let state = LutShortBuilder::new(pat)
+            .class(1, ByteClass::digit())
+            .build();
+        let config = &pat[..];
+        let found = LutShort::find_bytes(&config, &state, text);
+        assert_eq!(found, Some(8));
+    }
+
     #[test]
     fn test_short_matches() {
         let text = b"xxabcxxabcdxx";
@@ -422,6 +926,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_find_iter_overlapping() {
+        let text = b"aaaa";
+        let pat = b"aa";
+        let config = &pat[..];
+        let state = LutShort::build(&config);
+        let hits: Vec<usize> = LutShort::find_iter(&config, &state, text, true).collect();
+        assert_eq!(hits, vec![0, 1, 2]);
+
+        let hits: Vec<usize> = LutShort::find_iter(&config, &state, text, false).collect();
+        assert_eq!(hits, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_rfind() {
+        let text = b"abcXabcYabc";
+        let pat = b"abc";
+        let config = &pat[..];
+        let state = LutShort::build(&config);
+        assert_eq!(LutShort::rfind_bytes(&config, &state, text), Some(8));
+    }
+
     #[test]
     fn test_no_match() {
         let text = b"abcdefg";
@@ -432,6 +958,36 @@ mod tests {
         assert_eq!(found, None);
     }
 
+    #[test]
+    fn test_long_pattern_matches() {
+        // Patterns longer than 8 bytes used to fall back to scalar; the SIMD
+        // prefilter now handles any length by verifying the full slice.
+        let text = b"xxxhello_world_patternyyy";
+        let pat = b"hello_world_pattern";
+        let config = &pat[..];
+        let state = LutShort::build(&config);
+        let found = LutShort::find_bytes(&config, &state, text);
+        assert_eq!(found, Some(3));
+    }
+
+    #[test]
+    fn test_two_signature_pattern() {
+        // A pattern with two distinctive rare bytes ('q' and 'z') exercises the
+        // second-signature prefilter; matches must still be found exactly.
+        let text = b"a quartz mug, a quartz vase, and quartz again";
+        let pat = b"quartz";
+        let config = &pat[..];
+        let state = LutShort::build(&config);
+        let hits: Vec<usize> = LutShort::find_iter(&config, &state, text, false).collect();
+        let expected: Vec<usize> = text
+            .windows(pat.len())
+            .enumerate()
+            .filter(|(_, w)| *w == &pat[..])
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(hits, expected);
+    }
+
     #[test]
     fn test_too_long_pattern() {
         let text = b"abcdefg";
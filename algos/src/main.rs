@@ -1,9 +1,12 @@
 use std::fs::File;
 use std::io::{self, Read, Write};
-use std::path::PathBuf;
-use std::time::{Duration, Instant}; // Added Duration and Instant
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
-use algos::{BM, KMP, KmerConfig, KmerSearch, Naive, NaiveScalar, NaiveVectorized, StringSearch};
+use algos::{
+    AhoCorasick, BM, CompileOptions, KMP, KmerConfig, KmerSearch, Naive, NaiveScalar,
+    NaiveVectorized, StringSearch,
+};
 use clap::Parser;
 
 #[derive(Debug, Clone, clap::ValueEnum)]
@@ -14,6 +17,7 @@ enum Algorithm {
     Kmp,
     Bm,
     Kmer,
+    AhoCorasick,
 }
 
 /// Example:
@@ -35,13 +39,16 @@ struct Cli {
     #[arg(long)]
     like: bool,
 
+    /// Pattern to search for. May be repeated to search several patterns in
+    /// one pass (most useful with `--algo aho-corasick`).
     #[arg(
         long,
         conflicts_with = "pattern_file",
         required_unless_present = "pattern_file"
     )]
-    pattern: Option<String>,
+    pattern: Vec<String>,
 
+    /// File of patterns, one per line. Blank lines are ignored.
     #[arg(
         long = "pattern-file",
         value_name = "PATTERN_FILE",
@@ -73,6 +80,43 @@ struct Cli {
     /// Measure and print execution time for the search algorithm
     #[arg(long)]
     measure_time: bool,
+
+    /// Output format. `text` is the human-readable form; `json` emits one
+    /// newline-delimited JSON record per text for downstream tooling.
+    #[arg(long, value_enum, default_value = "text")]
+    format: Format,
+
+    /// Report each match as a 1-based line:column position (with the matched
+    /// line printed and the span marked) instead of a raw byte offset.
+    #[arg(long = "line-numbers")]
+    line_numbers: bool,
+
+    /// Match case-insensitively.
+    #[arg(short = 'i', long = "ignore-case")]
+    ignore_case: bool,
+
+    /// Case-insensitive unless a pattern contains an uppercase character.
+    #[arg(long = "smart-case", conflicts_with = "ignore_case")]
+    smart_case: bool,
+
+    /// Use a rare-byte SIMD prefilter for KMP and BM. Enabled automatically for
+    /// patterns of length >= AUTO_PREFILTER_MIN_LEN.
+    #[arg(long)]
+    prefilter: bool,
+
+    /// Number of measured iterations per text (only with --measure-time).
+    #[arg(long, default_value_t = 1)]
+    repeat: usize,
+
+    /// Number of warmup iterations discarded before measuring.
+    #[arg(long, default_value_t = 0)]
+    warmup: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum Format {
+    Text,
+    Json,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -87,9 +131,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .into());
     }
 
-    let pattern = load_pattern(&cli)?;
-    if pattern.is_empty() {
-        return Err("Pattern must not be empty".into());
+    let patterns = load_patterns(&cli)?;
+    if patterns.is_empty() || patterns.iter().any(|p| p.is_empty()) {
+        return Err("Patterns must not be empty".into());
     }
 
     let per_text_and_pattern_alpha = resolve_alphabet_sizes(cli.texts.len(), &cli.alphabet_sizes)?;
@@ -99,13 +143,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         None => Box::new(io::stdout()),
     };
 
-    writeln!(
-        out,
-        "# algorithm={:?}, encoding={}, pattern-length={}",
-        cli.algo,
-        encoding,
-        pattern.len()
-    )?;
+    if cli.format == Format::Text {
+        writeln!(
+            out,
+            "# algorithm={:?}, encoding={}, patterns={}",
+            cli.algo,
+            encoding,
+            patterns.len()
+        )?;
+    }
 
     for (idx, text_path) in cli.texts.iter().enumerate() {
         let text = load_text(text_path)?;
@@ -115,34 +161,179 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             .map(|v| (v[idx], v[v.len() - 1]))
             .unwrap_or((None, None));
 
-        if alpha_text.is_some() || alpha_pattern.is_some() {
-            writeln!(
-                out,
-                "# alphabet-size text={:?} pattern={:?} (for text {:?})",
-                alpha_text, alpha_pattern, text_path
-            )?;
+        let (matches, samples) = run_algorithm(&cli, &text, &patterns)?;
+
+        match cli.format {
+            Format::Text => {
+                if alpha_text.is_some() || alpha_pattern.is_some() {
+                    writeln!(
+                        out,
+                        "# alphabet-size text={:?} pattern={:?} (for text {:?})",
+                        alpha_text, alpha_pattern, text_path
+                    )?;
+                }
+
+                writeln!(out, "text={:?}", text_path)?;
+
+                if let Some(&first) = samples.first() {
+                    writeln!(out, "execution_time: {}ns", first)?;
+                }
+                if samples.len() > 1 {
+                    writeln!(out, "samples_ns: {:?}", samples)?;
+                }
+
+                if cli.line_numbers {
+                    let index = LineIndex::new(&text);
+                    for (idx, offsets) in matches.iter().enumerate() {
+                        for &offset in offsets {
+                            let (line, col) = index.locate(offset);
+                            let span = index.line_span(line);
+                            let content = &text[span.0..span.1];
+                            if patterns.len() == 1 {
+                                writeln!(out, "{}:{}: {}", line, col, content)?;
+                            } else {
+                                writeln!(out, "[{}] {}:{}: {}", idx, line, col, content)?;
+                            }
+                            let marker_col = col.saturating_sub(1);
+                            let len = patterns[idx].len().max(1);
+                            writeln!(
+                                out,
+                                "{}{}",
+                                " ".repeat(marker_col),
+                                "^".repeat(len)
+                            )?;
+                        }
+                    }
+                } else if patterns.len() == 1 {
+                    writeln!(out, "matches: {:?}", matches[0])?;
+                } else {
+                    for (idx, offsets) in matches.iter().enumerate() {
+                        writeln!(out, "matches[{}]: {:?}", idx, offsets)?;
+                    }
+                }
+                writeln!(out)?;
+            }
+            Format::Json => {
+                write_json_record(&mut out, &cli, text_path, &patterns, &matches, &samples)?;
+            }
         }
+    }
 
-        let (matches, duration) = run_algorithm(&cli, &text, &pattern)?;
-        
-        writeln!(out, "text={:?}", text_path)?;
-        
-        if let Some(d) = duration {
-            writeln!(out, "execution_time: {}ns", d.as_nanos())?;
-        }
-        
-        writeln!(out, "matches: {:?}", matches)?;
-        writeln!(out)?;
+    Ok(())
+}
+
+/// Write one newline-delimited JSON object describing the results for a text.
+fn write_json_record(
+    out: &mut dyn Write,
+    cli: &Cli,
+    text_path: &Path,
+    patterns: &[String],
+    matches: &[Vec<usize>],
+    samples: &[u128],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let algo = format!("{:?}", cli.algo);
+    let pattern_lengths: Vec<usize> = patterns.iter().map(|p| p.len()).collect();
+
+    let mut record = String::new();
+    record.push('{');
+    record.push_str(&format!("\"algorithm\":{},", json_string(&algo)));
+    record.push_str(&format!("\"text\":{},", json_string(&text_path.to_string_lossy())));
+    record.push_str(&format!("\"pattern_lengths\":{},", json_usize_array(&pattern_lengths)));
+    record.push_str(&format!("\"matches\":{}", json_matches(matches)));
+    if let Some(&first) = samples.first() {
+        record.push_str(&format!(",\"execution_time_ns\":{}", first));
+        let inner: Vec<String> = samples.iter().map(|s| s.to_string()).collect();
+        record.push_str(&format!(",\"samples_ns\":[{}]", inner.join(",")));
     }
+    record.push('}');
 
+    writeln!(out, "{}", record)?;
     Ok(())
 }
 
-fn load_pattern(cli: &Cli) -> Result<String, Box<dyn std::error::Error>> {
-    if let Some(ref pat) = cli.pattern {
-        Ok(pat.clone())
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_usize_array(values: &[usize]) -> String {
+    let inner: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    format!("[{}]", inner.join(","))
+}
+
+fn json_matches(matches: &[Vec<usize>]) -> String {
+    let inner: Vec<String> = matches.iter().map(|offsets| json_usize_array(offsets)).collect();
+    format!("[{}]", inner.join(","))
+}
+
+/// Precomputed newline offsets for a text, so byte offsets can be turned into
+/// 1-based line/column positions with a binary search per match.
+struct LineIndex {
+    text_len: usize,
+    /// Byte offset of the first character of each line.
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    fn new(text: &str) -> Self {
+        let mut line_starts = vec![0usize];
+        for (i, b) in text.bytes().enumerate() {
+            if b == b'\n' {
+                line_starts.push(i + 1);
+            }
+        }
+        Self {
+            text_len: text.len(),
+            line_starts,
+        }
+    }
+
+    /// Return the (1-based line, 1-based column) of a byte offset.
+    fn locate(&self, offset: usize) -> (usize, usize) {
+        // partition_point gives the number of line starts <= offset.
+        let line = self.line_starts.partition_point(|&s| s <= offset);
+        let col = offset - self.line_starts[line - 1] + 1;
+        (line, col)
+    }
+
+    /// Return the [start, end) byte span of a 1-based line, excluding the
+    /// trailing newline.
+    fn line_span(&self, line: usize) -> (usize, usize) {
+        let start = self.line_starts[line - 1];
+        let end = self
+            .line_starts
+            .get(line)
+            .map(|&next| next - 1)
+            .unwrap_or(self.text_len);
+        (start, end)
+    }
+}
+
+fn load_patterns(cli: &Cli) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    if !cli.pattern.is_empty() {
+        Ok(cli.pattern.clone())
     } else if let Some(ref path) = cli.pattern_file {
-        load_text(path)
+        let contents = load_text(path)?;
+        let patterns: Vec<String> = contents
+            .lines()
+            .map(|l| l.trim_end_matches('\r'))
+            .filter(|l| !l.is_empty())
+            .map(|l| l.to_string())
+            .collect();
+        Ok(patterns)
     } else {
         Err("Either --pattern or --pattern-file must be provided".into())
     }
@@ -190,35 +381,133 @@ fn resolve_alphabet_sizes(
     .into())
 }
 
+/// Run the selected algorithm over every pattern. Returns one offset list per
+/// pattern (indexed the same as `patterns`) and, when `--measure-time` is set,
+/// one nanosecond sample per measured iteration.
 fn run_algorithm(
     cli: &Cli,
     text: &str,
-    pattern: &str,
-) -> Result<(Vec<usize>, Option<Duration>), Box<dyn std::error::Error>> {
-    let start = if cli.measure_time {
-        Some(Instant::now())
+    patterns: &[String],
+) -> Result<(Vec<Vec<usize>>, Vec<u128>), Box<dyn std::error::Error>> {
+    // Case folding is byte-length preserving (ASCII lowercasing), so reported
+    // offsets stay aligned to the original text. Fold the text once and each
+    // pattern before handing them to the algorithms. This is preprocessing and
+    // stays outside the timed region.
+    let fold = fold_enabled(cli, patterns);
+    let folded_text = if fold {
+        text.to_ascii_lowercase()
+    } else {
+        text.to_string()
+    };
+    let folded_patterns: Vec<String> = if fold {
+        patterns.iter().map(|p| p.to_ascii_lowercase()).collect()
     } else {
-        None
+        patterns.to_vec()
     };
+    let text = folded_text.as_str();
+    let patterns = folded_patterns.as_slice();
+
+    if !cli.measure_time {
+        return Ok((search(cli, text, patterns), Vec::new()));
+    }
+
+    // Warmup iterations prime caches and branch predictors; their timings are
+    // discarded. The measured iterations follow and each yields one sample.
+    for _ in 0..cli.warmup {
+        let _ = search(cli, text, patterns);
+    }
+
+    let repeat = cli.repeat.max(1);
+    let mut samples = Vec::with_capacity(repeat);
+    let mut result = Vec::new();
+    for _ in 0..repeat {
+        let start = Instant::now();
+        result = search(cli, text, patterns);
+        samples.push(start.elapsed().as_nanos());
+    }
+
+    Ok((result, samples))
+}
 
-    let result = match cli.algo {
-        Algorithm::Naive => Naive::find_all((), text, pattern),
-        Algorithm::NaiveScalar => NaiveScalar::find_all((), text, pattern),
-        Algorithm::NaiveVectorized => NaiveVectorized::find_all((), text, pattern),
-        Algorithm::Kmp => KMP::find_all((), text, pattern),
-        Algorithm::Bm => BM::find_all((), text, pattern),
+/// Execute one full search of every pattern against `text`.
+fn search(cli: &Cli, text: &str, patterns: &[String]) -> Vec<Vec<usize>> {
+    match cli.algo {
+        Algorithm::AhoCorasick => {
+            // Single pass over the text reporting every pattern at once.
+            let automaton = AhoCorasick::build(patterns);
+            let mut per_pattern = vec![Vec::new(); patterns.len()];
+            for hit in automaton.find_all(text.as_bytes()) {
+                per_pattern[hit.pattern].push(hit.start);
+            }
+            per_pattern
+        }
+        _ => patterns
+            .iter()
+            .map(|pattern| run_single(cli, text, pattern))
+            .collect(),
+    }
+}
+
+/// Decide whether matching should fold case: always when `--ignore-case`, and
+/// under `--smart-case` only when no pattern contains an uppercase letter.
+fn fold_enabled(cli: &Cli, patterns: &[String]) -> bool {
+    if cli.ignore_case {
+        return true;
+    }
+    if cli.smart_case {
+        return !patterns.iter().any(|p| p.chars().any(|c| c.is_uppercase()));
+    }
+    false
+}
+
+fn run_single(cli: &Cli, text: &str, pattern: &str) -> Vec<usize> {
+    match cli.algo {
+        Algorithm::Naive => {
+            let cfg = Naive::compile(pattern, CompileOptions::default());
+            let state = Naive::build(&cfg);
+            Naive::find_all(&cfg, &state, text)
+        }
+        Algorithm::NaiveScalar => {
+            let cfg = NaiveScalar::compile(pattern, CompileOptions::default());
+            let state = NaiveScalar::build(&cfg);
+            NaiveScalar::find_all(&cfg, &state, text)
+        }
+        Algorithm::NaiveVectorized => {
+            let cfg = NaiveVectorized::compile(pattern, CompileOptions::default());
+            let state = NaiveVectorized::build(&cfg);
+            NaiveVectorized::find_all(&cfg, &state, text)
+        }
+        Algorithm::Kmp | Algorithm::Bm => {
+            let pattern_bytes = pattern.as_bytes();
+            let use_prefilter =
+                cli.prefilter || pattern_bytes.len() >= algos::AUTO_PREFILTER_MIN_LEN;
+            if use_prefilter {
+                // Jump to rare-byte candidates, then verify with a full compare
+                // (the shared verification step for both window algorithms).
+                algos::prefilter_find_all(text.as_bytes(), pattern_bytes, |text, start| {
+                    &text[start..start + pattern_bytes.len()] == pattern_bytes
+                })
+            } else if matches!(cli.algo, Algorithm::Kmp) {
+                let cfg = KMP::compile(pattern, CompileOptions::default());
+                let state = KMP::build(&cfg);
+                KMP::find_all(&cfg, &state, text)
+            } else {
+                let cfg = BM::compile(pattern, CompileOptions::default());
+                let state = BM::build(&cfg);
+                BM::find_all(&cfg, &state, text)
+            }
+        }
         Algorithm::Kmer => {
             let cfg = KmerConfig {
                 pattern: pattern.as_bytes().to_vec(),
                 k: cli.kmer_k,
                 min_hits: cli.kmer_min_hits,
+                max_mismatch: 0,
+                sketch_size: 0,
             };
-            let index = KmerSearch::build(cfg);
-            <KmerSearch as StringSearch>::find_all(index, text, pattern)
+            let state = KmerSearch::build(&cfg);
+            <KmerSearch as StringSearch>::find_all(&cfg, &state, text)
         }
-    };
-
-    let duration = start.map(|s| s.elapsed());
-
-    Ok((result, duration))
+        Algorithm::AhoCorasick => unreachable!("aho-corasick is handled as a batch"),
+    }
 }
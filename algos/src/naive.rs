@@ -1,42 +1,63 @@
-use crate::StringSearch;
+use crate::{CompileOptions, StringSearch};
 
 pub struct Naive;
 pub struct NaiveScalar;
 pub struct NaiveVectorized;
 
 impl StringSearch for Naive {
-    type Config = ();
+    type Config<'p> = &'p [u8];
     type State = ();
-    fn find_bytes(_state: Self::State, text: &[u8], pattern: &[u8]) -> Option<usize> {
-        naive_find(text, pattern)
+
+    fn compile(pattern: &str, _options: CompileOptions) -> Self::Config<'_> {
+        pattern.as_bytes()
     }
 
-    fn find_all_bytes(_state: Self::State, text: &[u8], pattern: &[u8]) -> Vec<usize> {
-        naive_find_all(text, pattern)
+    fn build(_config: &Self::Config<'_>) -> Self::State {}
+
+    fn find_bytes(config: &Self::Config<'_>, _state: &Self::State, text: &[u8]) -> Option<usize> {
+        naive_find(text, config)
+    }
+
+    fn find_all_bytes(config: &Self::Config<'_>, _state: &Self::State, text: &[u8]) -> Vec<usize> {
+        naive_find_all(text, config)
     }
 }
 
 impl StringSearch for NaiveScalar {
-    type Config = ();
+    type Config<'p> = &'p [u8];
     type State = ();
-    fn find_bytes(_state: Self::State, text: &[u8], pattern: &[u8]) -> Option<usize> {
-        naive_find_scalar(text, pattern)
+
+    fn compile(pattern: &str, _options: CompileOptions) -> Self::Config<'_> {
+        pattern.as_bytes()
     }
 
-    fn find_all_bytes(_state: Self::State, text: &[u8], pattern: &[u8]) -> Vec<usize> {
-        naive_find_all_scalar(text, pattern)
+    fn build(_config: &Self::Config<'_>) -> Self::State {}
+
+    fn find_bytes(config: &Self::Config<'_>, _state: &Self::State, text: &[u8]) -> Option<usize> {
+        naive_find_scalar(text, config)
+    }
+
+    fn find_all_bytes(config: &Self::Config<'_>, _state: &Self::State, text: &[u8]) -> Vec<usize> {
+        naive_find_all_scalar(text, config)
     }
 }
 
 impl StringSearch for NaiveVectorized {
-    type Config = ();
+    type Config<'p> = &'p [u8];
     type State = ();
-    fn find_bytes(_state: Self::State, text: &[u8], pattern: &[u8]) -> Option<usize> {
-        unsafe { neon::naive_find_neon(text, pattern) }
+
+    fn compile(pattern: &str, _options: CompileOptions) -> Self::Config<'_> {
+        pattern.as_bytes()
     }
 
-    fn find_all_bytes(_state: Self::State, text: &[u8], pattern: &[u8]) -> Vec<usize> {
-        unsafe { neon::naive_find_all_neon(text, pattern) }
+    fn build(_config: &Self::Config<'_>) -> Self::State {}
+
+    fn find_bytes(config: &Self::Config<'_>, _state: &Self::State, text: &[u8]) -> Option<usize> {
+        unsafe { neon::naive_find_neon(text, config) }
+    }
+
+    fn find_all_bytes(config: &Self::Config<'_>, _state: &Self::State, text: &[u8]) -> Vec<usize> {
+        unsafe { neon::naive_find_all_neon(text, config) }
     }
 }
 
@@ -116,17 +137,20 @@ pub mod neon {
             return None;
         }
 
-        let first = pattern[0];
-        let first_vec = unsafe { vdupq_n_u8(first) };
+        // Scan for the pattern's rarest byte rather than always byte 0: a common
+        // first byte (a space, `e`, …) makes nearly every chunk a false candidate.
+        let (k, rare) = crate::prefilter::rarest_pivot(pattern);
+        let rare_vec = unsafe { vdupq_n_u8(rare) };
         let chunk_size = 16;
 
         let mut i = 0;
 
-        // Vectorized scanning for the first byte of the pattern.
+        // Vectorized scanning for the rare byte; a lane hit at text position
+        // `cand` implies a window starting at `cand - k`.
         while i + chunk_size <= n {
             let ptr = unsafe { text.as_ptr().add(i) };
             let chunk = unsafe { vld1q_u8(ptr) };
-            let cmp = unsafe { vceqq_u8(chunk, first_vec) };
+            let cmp = unsafe { vceqq_u8(chunk, rare_vec) };
 
             let mut lanes = [0; 16];
             unsafe { vst1q_u8(lanes.as_mut_ptr(), cmp) };
@@ -134,8 +158,8 @@ pub mod neon {
             for (lane, &value) in lanes.iter().enumerate().take(chunk_size) {
                 if value == 0xFF {
                     let cand = i + lane;
-                    if cand + m <= n && &text[cand..cand + m] == pattern {
-                        return Some(cand);
+                    if cand >= k && cand - k + m <= n && &text[cand - k..cand - k + m] == pattern {
+                        return Some(cand - k);
                     }
                 }
             }
@@ -143,10 +167,13 @@ pub mod neon {
             i += chunk_size;
         }
 
-        // Scalar tail
-        while i + m <= n {
-            if &text[i..i + m] == pattern {
-                return Some(i);
+        // Scalar tail over the remaining rare-byte positions.
+        while i < n {
+            if text[i] == rare {
+                let cand = i;
+                if cand >= k && cand - k + m <= n && &text[cand - k..cand - k + m] == pattern {
+                    return Some(cand - k);
+                }
             }
             i += 1;
         }
@@ -173,17 +200,17 @@ pub mod neon {
             return result;
         }
 
-        let first = pattern[0];
-        let first_vec = unsafe { vdupq_n_u8(first) };
+        let (k, rare) = crate::prefilter::rarest_pivot(pattern);
+        let rare_vec = unsafe { vdupq_n_u8(rare) };
         let chunk_size = 16;
 
         let mut i = 0;
 
-        // Vectorized scanning, but collect *all* matches (overlapping allowed).
+        // Vectorized scanning on the rare byte, collecting *all* matches.
         while i + chunk_size <= n {
             let ptr = unsafe { text.as_ptr().add(i) };
             let chunk = unsafe { vld1q_u8(ptr) };
-            let cmp = unsafe { vceqq_u8(chunk, first_vec) };
+            let cmp = unsafe { vceqq_u8(chunk, rare_vec) };
 
             let mut lanes = [0u8; 16];
             unsafe { vst1q_u8(lanes.as_mut_ptr(), cmp) };
@@ -191,8 +218,8 @@ pub mod neon {
             for (lane, &value) in lanes.iter().enumerate().take(chunk_size) {
                 if value == 0xFF {
                     let cand = i + lane;
-                    if cand + m <= n && &text[cand..cand + m] == pattern {
-                        result.push(cand);
+                    if cand >= k && cand - k + m <= n && &text[cand - k..cand - k + m] == pattern {
+                        result.push(cand - k);
                     }
                 }
             }
@@ -200,10 +227,13 @@ pub mod neon {
             i += chunk_size;
         }
 
-        // Scalar tail for remaining positions
-        while i + m <= n {
-            if &text[i..i + m] == pattern {
-                result.push(i);
+        // Scalar tail over the remaining rare-byte positions.
+        while i < n {
+            if text[i] == rare {
+                let cand = i;
+                if cand >= k && cand - k + m <= n && &text[cand - k..cand - k + m] == pattern {
+                    result.push(cand - k);
+                }
             }
             i += 1;
         }
@@ -222,8 +252,11 @@ pub fn naive_find(text: &[u8], pattern: &[u8]) -> Option<usize> {
 
     #[cfg(not(all(target_arch = "aarch64", target_feature = "neon")))]
     {
-        log::debug!("naive_find: using scalar implementation");
-        naive_find_scalar(text, pattern)
+        log::debug!("naive_find: using rare-byte prefiltered scalar implementation");
+        if pattern.is_empty() {
+            return Some(0);
+        }
+        crate::prefilter::find_first_with(text, pattern, |t, s| &t[s..s + pattern.len()] == pattern)
     }
 }
 
@@ -237,8 +270,11 @@ pub fn naive_find_all(text: &[u8], pattern: &[u8]) -> Vec<usize> {
 
     #[cfg(not(all(target_arch = "aarch64", target_feature = "neon")))]
     {
-        debug!("naive_find_all: using scalar implementation");
-        naive_find_all_scalar(text, pattern)
+        log::debug!("naive_find_all: using rare-byte prefiltered scalar implementation");
+        if pattern.is_empty() {
+            return (0..=text.len()).collect();
+        }
+        crate::prefilter::find_all_with(text, pattern, |t, s| &t[s..s + pattern.len()] == pattern)
     }
 }
 
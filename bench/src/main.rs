@@ -25,12 +25,62 @@ const ALGORITHMS: &[&str] = &[
     "kmer",
 ];
 
+// Number of warmup and measured iterations per (algorithm, pattern, file).
+const WARMUP: usize = 3;
+const REPEAT: usize = 15;
+
 #[derive(Debug)]
 struct ResultEntry {
     algo: String,
     pattern: String,
     file: String,
-    duration_ns: u128,
+    samples_ns: Vec<u128>,
+}
+
+/// Summary statistics over a set of nanosecond samples.
+#[derive(Debug, Default)]
+struct Stats {
+    min: f64,
+    median: f64,
+    mean: f64,
+    stddev: f64,
+    outliers: usize,
+}
+
+impl Stats {
+    fn from_samples(samples: &[u128]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        let mut sorted: Vec<f64> = samples.iter().map(|&s| s as f64).collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let n = sorted.len();
+        let min = sorted[0];
+        let median = if n % 2 == 0 {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+        } else {
+            sorted[n / 2]
+        };
+        let mean = sorted.iter().sum::<f64>() / n as f64;
+        let variance = sorted.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n as f64;
+        let stddev = variance.sqrt();
+
+        // Count samples more than 3 standard deviations from the mean.
+        let outliers = sorted
+            .iter()
+            .filter(|&&v| (v - mean).abs() > 3.0 * stddev)
+            .count();
+
+        Self {
+            min,
+            median,
+            mean,
+            stddev,
+            outliers,
+        }
+    }
 }
 
 fn main() {
@@ -61,6 +111,10 @@ fn main() {
 
             let mut args = vec![
                 "--measure-time".to_string(),
+                "--warmup".to_string(),
+                WARMUP.to_string(),
+                "--repeat".to_string(),
+                REPEAT.to_string(),
                 "--pattern".to_string(),
                 pattern.to_string(),
                 "--algo".to_string(),
@@ -107,57 +161,103 @@ fn main() {
 fn parse_output(output: &str, algo: &str, pattern: &str) -> Vec<ResultEntry> {
     let mut entries = Vec::new();
     let mut current_file = String::new();
+    // Hold the single-shot timing until we know whether a `samples_ns` line
+    // with the full per-iteration vector follows.
+    let mut pending_single: Option<u128> = None;
+
+    let flush = |entries: &mut Vec<ResultEntry>,
+                 file: &str,
+                 single: &mut Option<u128>,
+                 samples: Vec<u128>| {
+        if samples.is_empty() {
+            return;
+        }
+        entries.push(ResultEntry {
+            algo: algo.to_string(),
+            pattern: pattern.to_string(),
+            file: file.to_string(),
+            samples_ns: samples,
+        });
+        *single = None;
+    };
 
     for line in output.lines() {
         let line = line.trim();
-        
+
         if line.starts_with("text=") {
+            // A new text record begins; emit any lone single-shot sample.
+            if let Some(ns) = pending_single.take() {
+                flush(&mut entries, &current_file, &mut pending_single, vec![ns]);
+            }
             current_file = line
                 .trim_start_matches("text=\"")
                 .trim_end_matches('"')
                 .to_string();
         }
-        
+
         if line.starts_with("execution_time:") {
             if let Some(ns_str) = line.split_whitespace().nth(1) {
                 let ns_val = ns_str.trim_end_matches("ns");
                 if let Ok(ns) = ns_val.parse::<u128>() {
-                    entries.push(ResultEntry {
-                        algo: algo.to_string(),
-                        pattern: pattern.to_string(),
-                        file: current_file.clone(),
-                        duration_ns: ns,
-                    });
+                    pending_single = Some(ns);
                 }
             }
         }
+
+        if line.starts_with("samples_ns:") {
+            let samples = parse_sample_vec(line);
+            flush(&mut entries, &current_file, &mut pending_single, samples);
+        }
     }
+
+    // Trailing single-shot record with no samples_ns line.
+    if let Some(ns) = pending_single.take() {
+        flush(&mut entries, &current_file, &mut pending_single, vec![ns]);
+    }
+
     entries
 }
 
+/// Parse a `samples_ns: [1, 2, 3]` line into a vector of nanoseconds.
+fn parse_sample_vec(line: &str) -> Vec<u128> {
+    let start = match line.find('[') {
+        Some(i) => i + 1,
+        None => return Vec::new(),
+    };
+    let end = line.rfind(']').unwrap_or(line.len());
+    line[start..end]
+        .split(',')
+        .filter_map(|tok| tok.trim().parse::<u128>().ok())
+        .collect()
+}
+
 fn print_summary_table(results: &[ResultEntry]) {
-    println!("\n\n{:=^80}", " RESULTS SUMMARY ");
+    println!("\n\n{:=^104}", " RESULTS SUMMARY ");
     println!(
-        "{:<18} | {:<15} | {:<25} | {:>15}",
-        "Algorithm", "Pattern", "File", "Time (µs)"
+        "{:<16} | {:<12} | {:<20} | {:>10} | {:>10} | {:>10} | {:>8} | {:>4}",
+        "Algorithm", "Pattern", "File", "Min (µs)", "Med (µs)", "Mean (µs)", "SD (µs)", "Out"
     );
-    println!("{:-^80}", "");
+    println!("{:-^104}", "");
 
     for entry in results {
-        let micros = entry.duration_ns as f64 / 1000.0;
-        
+        let stats = Stats::from_samples(&entry.samples_ns);
+
         let short_file = Path::new(&entry.file)
             .file_name()
             .unwrap_or_default()
             .to_string_lossy();
 
         println!(
-            "{:<18} | {:<15} | {:<25} | {:>15.2}",
-            entry.algo, 
-            entry.pattern.chars().take(12).collect::<String>(), 
-            short_file, 
-            micros
+            "{:<16} | {:<12} | {:<20} | {:>10.2} | {:>10.2} | {:>10.2} | {:>8.2} | {:>4}",
+            entry.algo,
+            entry.pattern.chars().take(12).collect::<String>(),
+            short_file,
+            stats.min / 1000.0,
+            stats.median / 1000.0,
+            stats.mean / 1000.0,
+            stats.stddev / 1000.0,
+            stats.outliers,
         );
     }
-    println!("{:=^80}", " END ");
+    println!("{:=^104}", " END ");
 }
@@ -1,11 +1,22 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{boxed::Box, vec::Vec};
+
 use algos::StringSearch;
-use like::{like_match, Pattern};
+use like::{like_match, like_match_with_offsets, Pattern};
 use storage::dataset::{DataSet, Row};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct Match<'a> {
     pub table: &'a str,
     pub row: &'a Row<'a>,
+    /// Byte offsets within `row.data` where the pattern's literal was found,
+    /// when the backend could resolve them (see
+    /// [`like::like_match_with_offsets`]). `None` for plain [`execute`] and
+    /// for pattern shapes that path doesn't cover.
+    pub offsets: Option<Box<[usize]>>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -29,6 +40,37 @@ where
                 matches.push(Match {
                     table: table_name,
                     row,
+                    offsets: None,
+                });
+            }
+        }
+    }
+
+    matches
+}
+
+/// Like [`execute`], but also reports where in each matched row the pattern's
+/// literal was found, driven through the `StringSearch::find_all_bytes`
+/// machinery via [`like::like_match_with_offsets`].
+pub fn execute_positions<'p, 'd, S>(
+    pattern: &Pattern<'p, S>,
+    dataset: &'d DataSet<'d>,
+) -> Vec<Match<'d>>
+where
+    S: StringSearch,
+{
+    let mut matches = Vec::new();
+
+    for table in dataset.tables.iter() {
+        let table_name = table.name.as_str();
+
+        for row in table.rows.iter() {
+            let (is_match, offsets) = like_match_with_offsets(pattern, row.data);
+            if is_match {
+                matches.push(Match {
+                    table: table_name,
+                    row,
+                    offsets: offsets.map(Vec::into_boxed_slice),
                 });
             }
         }
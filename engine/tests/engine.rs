@@ -5,7 +5,7 @@ use std::{
 };
 
 use algos::StdSearch;
-use engine::{execute, execute_all};
+use engine::{execute, execute_all, execute_positions};
 use like::compile_pattern;
 use storage::{
     dataset::{load_text_table, DataSet},
@@ -40,7 +40,7 @@ fn execute_matches_single_pattern() {
     };
 
     let pattern_str = "h%o";
-    let pattern = compile_pattern::<StdSearch, _, _>(pattern_str, (), |_, pat| pat);
+    let pattern = compile_pattern::<StdSearch>(pattern_str);
 
     let matches = execute(&pattern, &dataset);
     assert_eq!(matches.len(), 1);
@@ -48,6 +48,25 @@ fn execute_matches_single_pattern() {
     assert_eq!(matches[0].row.data, "hello");
 }
 
+#[test]
+fn execute_positions_reports_literal_offsets() {
+    let arena = BumpArena::new(4096);
+    let dir = make_temp_dir("execute_positions");
+    let file_path = dir.join("sample.txt");
+    write_file(&file_path, "the cat sat on the cat mat");
+
+    let table = load_text_table(&arena, &file_path).expect("load text table");
+    let dataset = DataSet {
+        tables: vec![table].into_boxed_slice(),
+    };
+
+    let pattern = compile_pattern::<StdSearch>("%cat%");
+
+    let matches = execute_positions(&pattern, &dataset);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].offsets.as_deref(), Some([4, 19].as_slice()));
+}
+
 #[test]
 fn execute_all_reports_pattern_index() {
     let arena = BumpArena::new(4096);
@@ -60,8 +79,8 @@ fn execute_all_reports_pattern_index() {
         tables: vec![table].into_boxed_slice(),
     };
 
-    let p0 = compile_pattern::<StdSearch, _, _>("h%o", (), |_, pat| pat);
-    let p1 = compile_pattern::<StdSearch, _, _>("z%", (), |_, pat| pat);
+    let p0 = compile_pattern::<StdSearch>("h%o");
+    let p1 = compile_pattern::<StdSearch>("z%");
     let patterns = vec![p0, p1];
 
     let matches = execute_all(&patterns, &dataset);
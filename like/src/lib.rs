@@ -1,6 +1,13 @@
-use std::marker::PhantomData;
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use algos::StringSearch;
+extern crate alloc;
+
+use alloc::{boxed::Box, format, string::{String, ToString}, vec::Vec};
+use core::marker::PhantomData;
+
+use algos::{AhoCorasick, StringSearch};
+
+pub use algos::CompileOptions;
 
 #[derive(Debug, PartialEq)]
 pub enum Token<'a> {
@@ -13,55 +20,32 @@ pub struct Pattern<'a, S: StringSearch> {
     tokens: Box<[Token<'a>]>,
     min_len: usize,
 
-    literal_configs: Box<[S::Config]>,
+    literal_configs: Box<[S::Config<'a>]>,
     literal_states: Box<[S::State]>,
 
     literal_underscore_is_wildcard: bool,
 
-    _marker: PhantomData<S>,
-}
-
-#[derive(Debug, Clone, Copy)]
-pub struct CompileOptions {
-    pub treat_underscore_as_literal: bool,
-    pub literal_underscore_is_wildcard: bool,
-}
+    // Every `Token::Literal`, in token order, registered as a needle in one
+    // shared Aho–Corasick automaton so [`like_match_multi`] can locate them all
+    // in a single pass. The i-th literal token is needle id `i`.
+    multi_ac: AhoCorasick,
 
-impl Default for CompileOptions {
-    fn default() -> Self {
-        Self {
-            treat_underscore_as_literal: false,
-            literal_underscore_is_wildcard: false,
-        }
-    }
+    _marker: PhantomData<S>,
 }
 
-pub fn compile_pattern<'a, S, D, F>(
-    pattern: &'a str,
-    user_data: D,
-    config_factory: F,
-) -> Pattern<'a, S>
+pub fn compile_pattern<'a, S>(pattern: &'a str) -> Pattern<'a, S>
 where
     S: StringSearch,
-    F: FnMut(&mut D, &'a str) -> S::Config,
 {
-    compile_pattern_with_options(
-        pattern,
-        user_data,
-        config_factory,
-        CompileOptions::default(),
-    )
+    compile_pattern_with_options(pattern, CompileOptions::default())
 }
 
-pub fn compile_pattern_with_options<'a, S, D, F>(
+pub fn compile_pattern_with_options<'a, S>(
     pattern: &'a str,
-    mut user_data: D,
-    mut config_factory: F,
     options: CompileOptions,
 ) -> Pattern<'a, S>
 where
     S: StringSearch,
-    F: FnMut(&mut D, &'a str) -> S::Config,
 {
     if options.literal_underscore_is_wildcard && !options.treat_underscore_as_literal {
         panic!("literal underscore wildcard requires treat_underscore_as_literal");
@@ -70,6 +54,7 @@ where
     let mut tokens = Vec::new();
     let mut literal_configs = Vec::new();
     let mut literal_states = Vec::new();
+    let mut literals: Vec<&str> = Vec::new();
     let mut start_idx = 0;
     let mut min_len = 0;
 
@@ -79,9 +64,10 @@ where
             if idx > start_idx {
                 let lit = &pattern[start_idx..idx];
                 tokens.push(Token::Literal(lit));
+                literals.push(lit);
 
                 // Generate config and state
-                let config = config_factory(&mut user_data, lit);
+                let config = S::compile(lit, options);
                 let state = S::build(&config);
 
                 literal_configs.push(config);
@@ -109,8 +95,9 @@ where
     if start_idx < pattern.len() {
         let lit = &pattern[start_idx..];
         tokens.push(Token::Literal(lit));
+        literals.push(lit);
 
-        let config = config_factory(&mut user_data, lit);
+        let config = S::compile(lit, options);
         let state = S::build(&config);
 
         literal_configs.push(config);
@@ -119,16 +106,170 @@ where
         min_len += lit.len();
     }
 
+    let multi_ac = AhoCorasick::build(&literals);
+
     Pattern {
         tokens: tokens.into_boxed_slice(),
         min_len,
         literal_configs: literal_configs.into_boxed_slice(),
         literal_states: literal_states.into_boxed_slice(),
         literal_underscore_is_wildcard: options.literal_underscore_is_wildcard,
+        multi_ac,
         _marker: PhantomData,
     }
 }
 
+/// Severity of a [`LikeDiagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The pattern is degenerate but still runs (e.g. matches everything).
+    Warning,
+    /// The pattern can never match the described input.
+    Error,
+}
+
+/// A structured finding about a `LIKE` pattern, analogous to redundant /
+/// unreachable match-arm analysis. Each carries a byte span into the original
+/// pattern string so callers can point users at the offending fragment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LikeDiagnostic {
+    pub severity: Severity,
+    pub kind: DiagnosticKind,
+    /// Byte range `[start, end)` in the source pattern.
+    pub span: (usize, usize),
+    pub message: String,
+}
+
+/// The class of a [`LikeDiagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// The pattern is made up solely of `%` and matches every input.
+    Irrefutable,
+    /// The pattern's minimum length exceeds the caller's maximum text length.
+    Unsatisfiable,
+    /// A `_` wrapped by `%` on both sides (`%_%`): the single-character skip is
+    /// positionally subsumed by the surrounding `Any`s.
+    RedundantSkip,
+}
+
+/// Compile a pattern and, alongside the runnable [`Pattern`], report structured
+/// diagnostics about its token stream. `max_text_len`, when given, flags
+/// patterns whose minimum length can never fit the target corpus.
+///
+/// This mirrors the tokenizer in [`compile_pattern_with_options`] over the
+/// source string so each finding can carry an exact span; the returned pattern
+/// is identical to what that function produces.
+pub fn compile_pattern_with_diagnostics<'a, S>(
+    pattern: &'a str,
+    options: CompileOptions,
+    max_text_len: Option<usize>,
+) -> (Pattern<'a, S>, Vec<LikeDiagnostic>)
+where
+    S: StringSearch,
+{
+    let compiled = compile_pattern_with_options::<S>(pattern, options);
+    let diagnostics = diagnose_pattern(pattern, options, max_text_len);
+    (compiled, diagnostics)
+}
+
+/// Kind tag used while walking the source pattern for [`diagnose_pattern`].
+enum SpanToken {
+    Literal,
+    Skip,
+    Any,
+}
+
+fn diagnose_pattern(
+    pattern: &str,
+    options: CompileOptions,
+    max_text_len: Option<usize>,
+) -> Vec<LikeDiagnostic> {
+    // Re-tokenize with spans, reproducing the wildcard rules of the real
+    // compiler (consecutive `_` merge into one `Skip`, runs of `%` collapse).
+    let mut spans: Vec<(SpanToken, usize, usize)> = Vec::new();
+    let mut min_len = 0usize;
+    let mut start_idx = 0;
+
+    let mut push_literal = |spans: &mut Vec<(SpanToken, usize, usize)>, lo: usize, hi: usize| {
+        spans.push((SpanToken::Literal, lo, hi));
+    };
+
+    for (idx, c) in pattern.char_indices() {
+        let is_wildcard = c == '%' || (c == '_' && !options.treat_underscore_as_literal);
+        if !is_wildcard {
+            continue;
+        }
+        if idx > start_idx {
+            push_literal(&mut spans, start_idx, idx);
+            min_len += idx - start_idx;
+        }
+        if c == '%' {
+            if !matches!(spans.last(), Some((SpanToken::Any, _, _))) {
+                spans.push((SpanToken::Any, idx, idx + 1));
+            }
+        } else {
+            // `_`: extend a trailing skip run, else open a new one.
+            if let Some((SpanToken::Skip, _, end)) = spans.last_mut() {
+                *end = idx + 1;
+            } else {
+                spans.push((SpanToken::Skip, idx, idx + 1));
+            }
+            min_len += 1;
+        }
+        start_idx = idx + c.len_utf8();
+    }
+    if start_idx < pattern.len() {
+        push_literal(&mut spans, start_idx, pattern.len());
+        min_len += pattern.len() - start_idx;
+    }
+
+    let mut out = Vec::new();
+
+    // Irrefutable: non-empty and nothing but `%`.
+    if !spans.is_empty() && spans.iter().all(|(t, _, _)| matches!(t, SpanToken::Any)) {
+        out.push(LikeDiagnostic {
+            severity: Severity::Warning,
+            kind: DiagnosticKind::Irrefutable,
+            span: (0, pattern.len()),
+            message: "pattern consists only of `%` and matches every input".to_string(),
+        });
+    }
+
+    // Unsatisfiable: cannot fit the caller's maximum text length.
+    if let Some(max) = max_text_len {
+        if min_len > max {
+            out.push(LikeDiagnostic {
+                severity: Severity::Error,
+                kind: DiagnosticKind::Unsatisfiable,
+                span: (0, pattern.len()),
+                message: format!(
+                    "pattern requires at least {min_len} bytes but the text is at most {max}"
+                ),
+            });
+        }
+    }
+
+    // Redundant `%_%`: a skip fenced by `Any` on both sides.
+    for i in 0..spans.len() {
+        if let (SpanToken::Skip, lo, hi) = &spans[i] {
+            let before = i.checked_sub(1).map(|j| &spans[j]);
+            let after = spans.get(i + 1);
+            if matches!(before, Some((SpanToken::Any, _, _)))
+                && matches!(after, Some((SpanToken::Any, _, _)))
+            {
+                out.push(LikeDiagnostic {
+                    severity: Severity::Warning,
+                    kind: DiagnosticKind::RedundantSkip,
+                    span: (*lo, *hi),
+                    message: "`_` between two `%` is positionally redundant".to_string(),
+                });
+            }
+        }
+    }
+
+    out
+}
+
 #[inline(always)]
 fn slice_from(text: &str, idx: usize) -> &str {
     debug_assert!(idx <= text.len());
@@ -308,20 +449,256 @@ pub fn like_match<S: StringSearch>(pattern: &Pattern<S>, text: &str) -> bool {
     true
 }
 
+/// Like [`like_match`], but for a pattern shaped exactly `%literal%` (a single
+/// literal token bounded by `Any` on both sides) also returns every byte
+/// offset at which the literal occurs, driven by the backend's
+/// [`StringSearch::find_all_bytes`] instead of stopping at the first hit.
+/// Other pattern shapes fall back to `like_match`'s boolean result with no
+/// offsets, since locating every occurrence of each token in a multi-literal
+/// pattern while respecting gap/order constraints is not yet wired through.
+pub fn like_match_with_offsets<S: StringSearch>(
+    pattern: &Pattern<S>,
+    text: &str,
+) -> (bool, Option<Vec<usize>>) {
+    if let [Token::Any, Token::Literal(_), Token::Any] = pattern.tokens.as_ref() {
+        let config = &pattern.literal_configs[0];
+        let state = &pattern.literal_states[0];
+        let offsets = S::find_all_bytes(config, state, text.as_bytes());
+        let is_match = !offsets.is_empty();
+        return (is_match, Some(offsets));
+    }
+    (like_match(pattern, text), None)
+}
+
+/// A gap between two required literals (or before the first / after the last).
+/// `flexible` is true when the gap contains an `Any` (`%`), so it admits *at
+/// least* `min_chars` intervening characters; otherwise the gap is made up only
+/// of `Skip`s and requires *exactly* `min_chars` characters.
+#[derive(Clone, Copy)]
+struct Gap {
+    flexible: bool,
+    min_chars: usize,
+}
+
+/// Byte index `min_chars` characters forward from `from`, or `None` if the text
+/// runs out of characters first.
+fn advance_chars(text: &str, from: usize, min_chars: usize) -> Option<usize> {
+    let mut idx = from;
+    let mut chars = text[from..].chars();
+    for _ in 0..min_chars {
+        idx += chars.next()?.len_utf8();
+    }
+    Some(idx)
+}
+
+/// Byte index of the start of the last `n` characters of `text[..to]`, or `None`
+/// if there are fewer than `n` characters.
+fn retreat_chars(text: &str, to: usize, n: usize) -> Option<usize> {
+    let mut count = 0;
+    let mut result = to;
+    for (bidx, _) in text[..to].char_indices().rev() {
+        result = bidx;
+        count += 1;
+        if count == n {
+            return Some(result);
+        }
+    }
+    if n == 0 {
+        Some(to)
+    } else if count == n {
+        Some(result)
+    } else {
+        None
+    }
+}
+
+/// Single-pass multi-literal `LIKE` matcher.
+///
+/// Rather than re-scanning the text for each `Any`-then-`Literal` jump (as
+/// [`like_match`] does), this locates every literal in one shared Aho–Corasick
+/// pass, then greedily places the literals in token order: literal *i+1* is
+/// accepted only at or after the end of literal *i*. `Any` tokens impose no
+/// positional constraint beyond order; `Skip(n)` requires exactly `n`
+/// intervening characters. The first/last literal is anchored when the pattern
+/// does not start/end with `%`. For `%a%b%c%d%`-style patterns this is a single
+/// linear scan plus order bookkeeping instead of O(segments × text).
+pub fn like_match_multi<S: StringSearch>(pattern: &Pattern<S>, text: &str) -> bool {
+    if text.len() < pattern.min_len {
+        return false;
+    }
+
+    // Fold the token stream into (literal lengths, surrounding gaps). Gap `i`
+    // precedes literal `i`; the final gap trails the last literal.
+    let mut lit_lens: Vec<usize> = Vec::new();
+    let mut gaps: Vec<Gap> = Vec::new();
+    let mut cur = Gap {
+        flexible: false,
+        min_chars: 0,
+    };
+    for token in pattern.tokens.iter() {
+        match token {
+            Token::Any => cur.flexible = true,
+            Token::Skip(n) => cur.min_chars += n,
+            Token::Literal(lit) => {
+                gaps.push(cur);
+                lit_lens.push(lit.len());
+                cur = Gap {
+                    flexible: false,
+                    min_chars: 0,
+                };
+            }
+        }
+    }
+    gaps.push(cur);
+
+    let r = lit_lens.len();
+
+    // No literals: the whole pattern is one gap of wildcards.
+    if r == 0 {
+        let g = gaps[0];
+        let total = text.chars().count();
+        return if g.flexible {
+            total >= g.min_chars
+        } else {
+            total == g.min_chars
+        };
+    }
+
+    // Bucket literal occurrences by needle id (== literal index), ascending.
+    let mut occ: Vec<Vec<usize>> = vec![Vec::new(); r];
+    for hit in pattern.multi_ac.find_all(text.as_bytes()) {
+        occ[hit.pattern].push(hit.start);
+    }
+    for starts in &mut occ {
+        starts.sort_unstable();
+    }
+
+    // Literals joined by *fixed* `Skip` gaps form a rigid block whose internal
+    // offsets are fully determined by the block's first-literal position; blocks
+    // are separated by *flexible* (`Any`) gaps and can float independently. We
+    // place blocks greedily left-to-right: within a flexible separator the
+    // leftmost feasible block placement maximises the room left for the rest.
+    let trailing = gaps[r];
+    let mut cursor = 0usize; // byte position just past the previous block
+    let mut i = 0;
+    while i < r {
+        let lead = gaps[i];
+        let mut j = i + 1;
+        while j < r && !gaps[j].flexible {
+            j += 1;
+        }
+        let is_last_block = j == r;
+
+        let min_start = match advance_chars(text, cursor, lead.min_chars) {
+            Some(b) => b,
+            None => return false,
+        };
+
+        // Verify the rigid block anchored at first-literal start `p`, returning
+        // (last-literal start, block end byte) when every literal lands. `bi`/`bj`
+        // are copies so the closure does not borrow the loop cursor `i`.
+        let (bi, bj) = (i, j);
+        let verify = |p: usize| -> Option<(usize, usize)> {
+            if occ[bi].binary_search(&p).is_err() {
+                return None;
+            }
+            let mut last_start = p;
+            let mut c = p + lit_lens[bi];
+            for k in (bi + 1)..bj {
+                let s = advance_chars(text, c, gaps[k].min_chars)?;
+                if occ[k].binary_search(&s).is_err() {
+                    return None;
+                }
+                last_start = s;
+                c = s + lit_lens[k];
+            }
+            Some((last_start, c))
+        };
+
+        // A fixed lead (only possible for the first block) pins the start; a
+        // flexible lead allows any start at or after `min_start`.
+        let block_end = if is_last_block && !trailing.flexible {
+            // Suffix-exact: the last literal must leave exactly `min_chars`
+            // characters, pinning its end and — through the rigid block — the
+            // first-literal start that yields it.
+            let req_end = match retreat_chars(text, text.len(), trailing.min_chars) {
+                Some(e) => e,
+                None => return false,
+            };
+            let last = j - 1;
+            let req_last_start = match req_end.checked_sub(lit_lens[last]) {
+                Some(s) => s,
+                None => return false,
+            };
+            let mut found = None;
+            for &p in occ[i].iter() {
+                if p < min_start {
+                    continue;
+                }
+                if !lead.flexible && p != min_start {
+                    break;
+                }
+                if let Some((ls, end)) = verify(p) {
+                    if ls == req_last_start {
+                        found = Some(end);
+                        break;
+                    }
+                }
+                if !lead.flexible {
+                    break;
+                }
+            }
+            match found {
+                Some(e) => e,
+                None => return false,
+            }
+        } else {
+            let mut found = None;
+            for &p in occ[i].iter() {
+                if p < min_start {
+                    continue;
+                }
+                if !lead.flexible && p != min_start {
+                    break;
+                }
+                if let Some((_, end)) = verify(p) {
+                    found = Some(end);
+                    break;
+                }
+                if !lead.flexible {
+                    break;
+                }
+            }
+            let end = match found {
+                Some(e) => e,
+                None => return false,
+            };
+            if is_last_block && text[end..].chars().count() < trailing.min_chars {
+                return false;
+            }
+            end
+        };
+
+        cursor = block_end;
+        i = j;
+    }
+
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use algos::{FftConfig, FftStr1, Naive, StdSearch, BM, KMP};
+    use algos::{FftStr1, Naive, StdSearch, BM, KMP};
 
-    fn run_test_suite<S, F>(factory: F)
+    fn run_test_suite<S>()
     where
         S: StringSearch,
-        F: FnMut(&mut (), &'static str) -> S::Config + Clone,
     {
         // Wrapper to simplify the calls inside tests
         macro_rules! compile {
             ($pat:expr) => {
-                compile_pattern::<S, _, _>($pat, (), factory.clone())
+                compile_pattern::<S>($pat)
             };
         }
 
@@ -365,30 +742,90 @@ mod tests {
         assert!(like_match(&p, "ðŸ’©more"));
     }
 
+    #[test]
+    fn test_pattern_diagnostics() {
+        let opts = CompileOptions::default();
+
+        let (_, diags) = compile_pattern_with_diagnostics::<StdSearch>("%%", opts, None);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, DiagnosticKind::Irrefutable);
+        assert_eq!(diags[0].severity, Severity::Warning);
+
+        let (_, diags) = compile_pattern_with_diagnostics::<StdSearch>("abc", opts, Some(2));
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, DiagnosticKind::Unsatisfiable);
+        assert_eq!(diags[0].severity, Severity::Error);
+
+        let (_, diags) = compile_pattern_with_diagnostics::<StdSearch>("a%_%b", opts, None);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, DiagnosticKind::RedundantSkip);
+        assert_eq!(diags[0].span, (2, 3));
+
+        let (_, diags) = compile_pattern_with_diagnostics::<StdSearch>("a%b", opts, None);
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn test_like_match_with_offsets_single_literal() {
+        let p = compile_pattern::<StdSearch>("%cat%");
+
+        let (is_match, offsets) = like_match_with_offsets(&p, "the cat sat on the cat mat");
+        assert!(is_match);
+        assert_eq!(offsets, Some(vec![4, 19]));
+
+        let (is_match, offsets) = like_match_with_offsets(&p, "no feline here");
+        assert!(!is_match);
+        assert_eq!(offsets, Some(vec![]));
+    }
+
+    #[test]
+    fn test_like_match_with_offsets_falls_back_for_other_shapes() {
+        let p = compile_pattern::<StdSearch>("a%b%c");
+        let (is_match, offsets) = like_match_with_offsets(&p, "aXbYc");
+        assert!(is_match);
+        assert_eq!(offsets, None);
+    }
+
+    #[test]
+    fn test_like_match_multi_agrees_with_like_match() {
+        let patterns = [
+            "abc", "%abc", "abc%", "%abc%", "a%b%c", "%a%b%c%", "a_c", "a__c", "a_%_b", "%a_b%",
+            "_abc", "abc_", "%", "%%", "_", "a%", "%a", "a%b", "%cat%dog%",
+        ];
+        let texts = [
+            "abc", "xabc", "abcx", "xabcx", "aXbYc", "abc", "azbzc", "a_c", "ac", "axxc",
+            "aXbc", "zabcz", "", "hello", "q", "cat and dog", "the cat met a dog here", "aaa",
+        ];
+        for p in patterns {
+            let compiled = compile_pattern::<StdSearch>(p);
+            for t in texts {
+                assert_eq!(
+                    like_match_multi(&compiled, t),
+                    like_match(&compiled, t),
+                    "pattern {p:?} text {t:?}",
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_std_algorithm() {
-        run_test_suite::<StdSearch, _>(|_, pat| unsafe { std::mem::transmute::<&str, &str>(pat) });
+        run_test_suite::<StdSearch>();
     }
 
     #[test]
     fn test_kmp_algorithm() {
-        run_test_suite::<KMP, _>(|_, pat| unsafe {
-            std::mem::transmute::<&[u8], &[u8]>(pat.as_bytes())
-        });
+        run_test_suite::<KMP>();
     }
 
     #[test]
     fn test_naive_algorithm() {
-        run_test_suite::<Naive, _>(|_, pat| unsafe {
-            std::mem::transmute::<&[u8], &[u8]>(pat.as_bytes())
-        });
+        run_test_suite::<Naive>();
     }
 
     #[test]
     fn test_bm_algorithm() {
-        run_test_suite::<BM, _>(|_, pat| unsafe {
-            std::mem::transmute::<&[u8], &[u8]>(pat.as_bytes())
-        });
+        run_test_suite::<BM>();
     }
 
     #[test]
@@ -397,12 +834,7 @@ mod tests {
             treat_underscore_as_literal: true,
             literal_underscore_is_wildcard: false,
         };
-        let pattern = compile_pattern_with_options::<StdSearch, _, _>(
-            "%a_c%",
-            (),
-            |_, pat| unsafe { std::mem::transmute::<&str, &str>(pat) },
-            options,
-        );
+        let pattern = compile_pattern_with_options::<StdSearch>("%a_c%", options);
 
         assert!(like_match(&pattern, "zza_czz"));
         assert!(!like_match(&pattern, "zzabczz"));
@@ -415,12 +847,7 @@ mod tests {
             treat_underscore_as_literal: true,
             literal_underscore_is_wildcard: true,
         };
-        let pattern = compile_pattern_with_options::<FftStr1, _, _>(
-            "%a_%_b%",
-            (),
-            |_, pat| FftConfig::from_str(pat),
-            options,
-        );
+        let pattern = compile_pattern_with_options::<FftStr1>("%a_%_b%", options);
         let text = "zzaXfooYbzz";
         let first_config = &pattern.literal_configs[0];
         let first_state = &pattern.literal_states[0];
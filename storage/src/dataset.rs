@@ -1,16 +1,27 @@
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+#[cfg(feature = "std")]
 use std::{
     fs,
+    io::{ErrorKind, Read},
     path::{Path, PathBuf},
 };
 
+use algos::{LutShort, StringSearch};
+
+use crate::interner::{Interner, Symbol};
 use crate::{fasta, BumpArena};
+#[cfg(feature = "std")]
+use crate::delimited::ByteLimit;
 
+#[cfg(feature = "std")]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SourceKind {
     Text,
     Fasta,
 }
 
+#[cfg(feature = "std")]
 #[derive(Debug, Clone)]
 pub struct Source {
     pub path: PathBuf,
@@ -19,8 +30,8 @@ pub struct Source {
 
 #[derive(Debug, Clone)]
 pub struct Row<'a> {
-    pub id: &'a str,
-    pub desc: &'a str,
+    pub id: Symbol,
+    pub desc: Symbol,
     pub data: &'a str,
 }
 
@@ -28,6 +39,15 @@ pub struct Row<'a> {
 pub struct Table<'a> {
     pub name: String,
     pub rows: Box<[Row<'a>]>,
+    /// Atom table backing every row's `id`/`desc` handle in this table.
+    pub interner: Interner<'a>,
+}
+
+impl<'a> Table<'a> {
+    /// Resolve a row's `id`/`desc` [`Symbol`] back to its string.
+    pub fn resolve(&self, sym: Symbol) -> &'a str {
+        self.interner.resolve(sym)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -35,33 +55,122 @@ pub struct DataSet<'a> {
     pub tables: Box<[Table<'a>]>,
 }
 
-pub fn load_text_table<'a>(arena: &'a BumpArena, path: &Path) -> Result<Table<'a>, String> {
-    let raw_string = fs::read_to_string(path)
-        .map_err(|e| format!("Failed to read text file {}: {}", path.display(), e))?;
+impl<'a> Table<'a> {
+    /// Search every row's `data` for `pattern` with the crate's SIMD matcher,
+    /// calling `on_hit(table_name, row_index, byte_offset)` for each
+    /// non-overlapping match. The `LutShortState` is built once and reused
+    /// across rows, so this is a single columnar scan rather than a per-row
+    /// rebuild.
+    pub fn search<F: FnMut(&str, usize, usize)>(&self, pattern: &[u8], mut on_hit: F) {
+        if pattern.is_empty() {
+            return;
+        }
+        let config: &[u8] = pattern;
+        let state = LutShort::build(&config);
+        for (row_index, row) in self.rows.iter().enumerate() {
+            let text = row.data.as_bytes();
+            for offset in LutShort::find_iter(&config, &state, text, false) {
+                on_hit(&self.name, row_index, offset);
+            }
+        }
+    }
 
-    let data = arena.alloc_str(&raw_string);
-    let file_name = filename_from_path(path)?;
-    let id = arena.alloc_str(&file_name);
+    /// Streaming search that charges each scanned row against `limit` before
+    /// looking at it, so a large column can be grepped without scanning past a
+    /// byte budget. Returns `true` if the whole column was searched and `false`
+    /// if the limit stopped the scan early.
+    #[cfg(feature = "std")]
+    pub fn search_streaming<F: FnMut(&str, usize, usize)>(
+        &self,
+        pattern: &[u8],
+        limit: &mut ByteLimit,
+        mut on_hit: F,
+    ) -> bool {
+        if pattern.is_empty() {
+            return true;
+        }
+        let config: &[u8] = pattern;
+        let state = LutShort::build(&config);
+        for (row_index, row) in self.rows.iter().enumerate() {
+            let text = row.data.as_bytes();
+            if !limit.try_reserve(text.len()) {
+                return false;
+            }
+            for offset in LutShort::find_iter(&config, &state, text, false) {
+                on_hit(&self.name, row_index, offset);
+            }
+        }
+        true
+    }
+}
 
-    let row = Row { id, desc: "", data };
+/// Read exactly `len` bytes from `reader` directly into a freshly reserved
+/// arena span and wrap the result as a single-row `Table`, skipping the
+/// `fs::read_to_string` + `alloc_str` pattern's intermediate heap `String` (and
+/// with it, the second full-file copy). `len` must match the reader's
+/// remaining length; a short read (the file shrinking mid-read) is reported as
+/// an `Err` rather than silently truncating the row.
+#[cfg(feature = "std")]
+pub fn load_text_table_streaming<'a, R: Read>(
+    arena: &'a BumpArena,
+    mut reader: R,
+    len: usize,
+    name: &str,
+) -> Result<Table<'a>, String> {
+    let buf = arena.alloc_uninit_bytes(len);
+    match reader.read_exact(buf) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => {
+            return Err(format!(
+                "Failed to read text file {name}: file shrank to fewer than the expected {len} bytes"
+            ));
+        }
+        Err(e) => return Err(format!("Failed to read text file {name}: {e}")),
+    }
+
+    let data = core::str::from_utf8(buf)
+        .map_err(|e| format!("Failed to read text file {name}: not valid UTF-8 ({e})"))?;
+
+    let interner = Interner::new(arena);
+    let row = Row {
+        id: interner.intern(name),
+        desc: interner.intern(""),
+        data,
+    };
 
     Ok(Table {
-        name: file_name,
+        name: name.to_string(),
         rows: Box::new([row]),
+        interner,
     })
 }
 
+#[cfg(feature = "std")]
+pub fn load_text_table<'a>(arena: &'a BumpArena, path: &Path) -> Result<Table<'a>, String> {
+    let file = fs::File::open(path)
+        .map_err(|e| format!("Failed to read text file {}: {}", path.display(), e))?;
+    let len = file
+        .metadata()
+        .map_err(|e| format!("Failed to read text file {}: {}", path.display(), e))?
+        .len() as usize;
+    let file_name = filename_from_path(path)?;
+
+    load_text_table_streaming(arena, file, len, &file_name)
+}
+
+#[cfg(feature = "std")]
 pub fn load_fasta_table<'a>(arena: &'a BumpArena, path: &Path) -> Result<Table<'a>, String> {
     let raw_bytes = fs::read(path)
         .map_err(|e| format!("Failed to read FASTA file {}: {}", path.display(), e))?;
 
     let entries = fasta::parse_fasta_into_arena(arena, &raw_bytes)?;
 
+    let interner = Interner::new(arena);
     let rows: Vec<Row<'a>> = entries
         .iter()
         .map(|entry| Row {
-            id: entry.id,
-            desc: entry.desc,
+            id: interner.intern(entry.id),
+            desc: interner.intern(entry.desc),
             data: entry.data,
         })
         .collect();
@@ -69,9 +178,11 @@ pub fn load_fasta_table<'a>(arena: &'a BumpArena, path: &Path) -> Result<Table<'
     Ok(Table {
         name: filename_from_path(path)?,
         rows: rows.into_boxed_slice(),
+        interner,
     })
 }
 
+#[cfg(feature = "std")]
 pub fn load_dataset<'a>(arena: &'a BumpArena, sources: &[Source]) -> Result<DataSet<'a>, String> {
     let mut tables = Vec::with_capacity(sources.len());
 
@@ -89,6 +200,7 @@ pub fn load_dataset<'a>(arena: &'a BumpArena, sources: &[Source]) -> Result<Data
     })
 }
 
+#[cfg(feature = "std")]
 pub fn load_dataset_from_paths<'a>(
     arena: &'a BumpArena,
     paths: &[PathBuf],
@@ -104,6 +216,7 @@ pub fn load_dataset_from_paths<'a>(
     load_dataset(arena, &sources)
 }
 
+#[cfg(feature = "std")]
 pub fn infer_source_kind(path: &Path) -> SourceKind {
     match path
         .extension()
@@ -116,6 +229,7 @@ pub fn infer_source_kind(path: &Path) -> SourceKind {
     }
 }
 
+#[cfg(feature = "std")]
 fn filename_from_path(path: &Path) -> Result<String, String> {
     path.file_name()
         .ok_or_else(|| format!("Missing filename for path {}", path.display()))
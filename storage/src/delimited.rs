@@ -4,6 +4,7 @@ use csv::{ReaderBuilder, Trim};
 
 use crate::{
     dataset::{Row, Table},
+    interner::Interner,
     BumpArena,
 };
 
@@ -36,6 +37,33 @@ pub struct ByteLimit {
     pub current: usize,
 }
 
+/// Failure modes of [`load_delimited_columns`]. A row dropped because it would
+/// exceed the configured [`ByteLimit`] is *not* an error — it is silently
+/// skipped — so this type only carries genuine failures, letting callers tell
+/// "row skipped due to limit" apart from "allocation failed".
+#[derive(Debug)]
+pub enum LoadError {
+    /// The file could not be opened or a record could not be parsed.
+    Io(String),
+    /// The arena ran out of space while storing a field.
+    OutOfMemory { table: String, bytes: usize },
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Io(msg) => write!(f, "{}", msg),
+            LoadError::OutOfMemory { table, bytes } => write!(
+                f,
+                "arena exhausted storing {} bytes for column '{}'",
+                bytes, table
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
 impl ByteLimit {
     pub fn new(max_bytes: usize) -> Self {
         Self {
@@ -59,9 +87,14 @@ pub fn load_delimited_columns<'a>(
     options: &DelimitedOptions,
     columns: &[ColumnSpec],
     limit: &mut Option<ByteLimit>,
-) -> Result<Vec<Table<'a>>, String> {
-    let file = File::open(path)
-        .map_err(|e| format!("Failed to read delimited file {}: {}", path.display(), e))?;
+) -> Result<Vec<Table<'a>>, LoadError> {
+    let file = File::open(path).map_err(|e| {
+        LoadError::Io(format!(
+            "Failed to read delimited file {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
 
     let mut reader = ReaderBuilder::new()
         .delimiter(options.delimiter)
@@ -75,10 +108,13 @@ pub fn load_delimited_columns<'a>(
         .from_reader(file);
 
     let mut rows_by_column: Vec<Vec<Row<'a>>> = vec![Vec::new(); columns.len()];
+    // One atom table per output column; delimited rows carry no id/desc, so
+    // these only ever hold the shared empty string.
+    let interners: Vec<Interner<'a>> = (0..columns.len()).map(|_| Interner::new(arena)).collect();
 
     for record_result in reader.records() {
-        let record =
-            record_result.map_err(|e| format!("CSV parse error in {}: {}", path.display(), e))?;
+        let record = record_result
+            .map_err(|e| LoadError::Io(format!("CSV parse error in {}: {}", path.display(), e)))?;
 
         if let Some(limit) = limit.as_mut() {
             let mut bytes = 0usize;
@@ -92,21 +128,34 @@ pub fn load_delimited_columns<'a>(
 
         for (idx, spec) in columns.iter().enumerate() {
             let value = record.get(spec.index).unwrap_or("");
-            let data = arena.alloc_str(value);
+            // Push the cap into the arena: a genuine exhaustion is a structured
+            // error, distinct from a row skipped above because of `ByteLimit`.
+            let data = arena
+                .try_alloc_with(value.len(), || value)
+                .ok_or_else(|| LoadError::OutOfMemory {
+                    table: spec.name.clone(),
+                    bytes: value.len(),
+                })?;
+            let empty = interners[idx].intern("");
             rows_by_column[idx].push(Row {
-                id: "",
-                desc: "",
+                id: empty,
+                desc: empty,
                 data,
             });
         }
     }
 
-    let file_name = filename_from_path(path)?;
+    let file_name = filename_from_path(path).map_err(LoadError::Io)?;
     let mut tables = Vec::with_capacity(columns.len());
-    for (spec, rows) in columns.iter().zip(rows_by_column.into_iter()) {
+    for ((spec, rows), interner) in columns
+        .iter()
+        .zip(rows_by_column.into_iter())
+        .zip(interners.into_iter())
+    {
         tables.push(Table {
             name: format!("{}.{}", file_name, spec.name),
             rows: rows.into_boxed_slice(),
+            interner,
         });
     }
 
@@ -1,4 +1,7 @@
-use std::str;
+use core::str;
+
+use alloc::{borrow::Cow, boxed::Box, string::String, vec::Vec};
+use hashbrown::HashMap;
 
 use crate::BumpArena;
 
@@ -9,7 +12,58 @@ pub struct FastaEntry<'a> {
     pub data: &'a str,
 }
 
+/// A single FASTQ record: header id/description, sequence, and the
+/// same-length quality string, stored alongside [`FastaEntry`] since
+/// read-subsampling and similar workflows routinely mix both formats.
+#[derive(Debug, Clone)]
+pub struct FastqEntry<'a> {
+    pub id: &'a str,
+    pub desc: &'a str,
+    pub data: &'a str,
+    pub qual: &'a str,
+}
+
+/// Locate the next line starting at `ptr`: the line's content with any
+/// trailing `\r` stripped, and the byte offset just past its `\n` (or past
+/// the end of `raw_bytes`, for a final unterminated line). This is the
+/// parser's hot loop on gigabyte inputs, so the newline search goes through
+/// `memchr`, which compiles to a vectorized SIMD scan instead of a
+/// byte-at-a-time loop.
+#[inline]
+fn take_line(raw_bytes: &[u8], ptr: usize) -> (&[u8], usize) {
+    let end = memchr::memchr(b'\n', &raw_bytes[ptr..])
+        .map(|i| ptr + i)
+        .unwrap_or(raw_bytes.len());
+
+    let mut line = &raw_bytes[ptr..end];
+    if line.ends_with(b"\r") {
+        line = &line[..line.len() - 1];
+    }
+
+    (line, end + 1)
+}
+
+/// Scalar twin of [`take_line`], kept only so `extensive_tests` can benchmark
+/// the win `memchr`'s vectorized scan gives over a hand-rolled byte loop.
+#[cfg(feature = "extensive_tests")]
+#[inline]
+fn take_line_scalar(raw_bytes: &[u8], ptr: usize) -> (&[u8], usize) {
+    let end = raw_bytes[ptr..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map(|i| ptr + i)
+        .unwrap_or(raw_bytes.len());
+
+    let mut line = &raw_bytes[ptr..end];
+    if line.ends_with(b"\r") {
+        line = &line[..line.len() - 1];
+    }
+
+    (line, end + 1)
+}
+
 /// Parses a FASTA byte slice and stores the strings into the Arena.
+#[cfg(feature = "std")]
 pub fn parse_fasta_into_arena<'a>(
     arena: &'a BumpArena,
     raw_bytes: &[u8],
@@ -18,24 +72,18 @@ pub fn parse_fasta_into_arena<'a>(
 
     let mut current_header: Option<(&'a str, &'a str)> = None;
 
-    // this is unnecessary tbh.
+    // this is unnecessary tbh. [`parse_fasta_borrowed`] below skips this
+    // buffer-then-copy for the common single-line case by borrowing straight
+    // out of `raw_bytes`, but this arena-backed entry point always owns its
+    // data, so every sequence line still goes through here regardless.
     let mut current_seq_buf: Vec<u8> = Vec::with_capacity(4096);
 
     let mut ptr = 0;
     while ptr < raw_bytes.len() {
-        let end = raw_bytes[ptr..]
-            .iter()
-            .position(|&b| b == b'\n')
-            .map(|i| ptr + i)
-            .unwrap_or(raw_bytes.len());
-
-        let mut line_content = &raw_bytes[ptr..end];
-        if line_content.ends_with(b"\r") {
-            line_content = &line_content[..line_content.len() - 1];
-        }
+        let (line_content, next) = take_line(raw_bytes, ptr);
 
         if line_content.is_empty() {
-            ptr = end + 1;
+            ptr = next;
             continue;
         }
 
@@ -79,7 +127,7 @@ pub fn parse_fasta_into_arena<'a>(
             current_seq_buf.extend_from_slice(line_content);
         }
 
-        ptr = end + 1;
+        ptr = next;
     }
 
     if let Some((stored_id, stored_desc)) = current_header {
@@ -98,6 +146,453 @@ pub fn parse_fasta_into_arena<'a>(
     Ok(entries.into_boxed_slice())
 }
 
+/// Parses a FASTQ byte slice and stores the strings into the Arena.
+///
+/// Each record is the standard four-line shape: a `@`-prefixed header, the
+/// sequence, a `+`-prefixed separator (optionally repeating the id), and a
+/// quality line. Sequence and quality are each allowed to wrap across
+/// multiple lines; since a quality character can legally be `@`, the
+/// wrapped quality block can't be split on "next line starting with `@`" --
+/// instead quality lines are accumulated until they reach the sequence's
+/// already-known length, which is what actually marks the record boundary.
+#[cfg(feature = "std")]
+pub fn parse_fastq_into_arena<'a>(
+    arena: &'a BumpArena,
+    raw_bytes: &[u8],
+) -> Result<Box<[FastqEntry<'a>]>, String> {
+    let mut entries = Vec::new();
+    let mut ptr = 0;
+
+    while ptr < raw_bytes.len() {
+        let (line, next) = take_line(raw_bytes, ptr);
+        if line.is_empty() {
+            ptr = next;
+            continue;
+        }
+        if line[0] != b'@' {
+            return Err(format!(
+                "Parse Error: expected a '@'-prefixed FASTQ header at byte offset {ptr}"
+            ));
+        }
+
+        let header_text = &line[1..];
+        let space_pos = header_text.iter().position(|&b| b == b' ');
+        let (raw_id, raw_desc) = match space_pos {
+            Some(p) => (&header_text[..p], &header_text[p + 1..]),
+            None => (header_text, &[] as &[u8]),
+        };
+        let id_str = str::from_utf8(raw_id)
+            .map_err(|_| "Invalid UTF-8 in FASTQ Header ID".to_string())?;
+        let desc_str = str::from_utf8(raw_desc)
+            .map_err(|_| format!("Invalid UTF-8 in FASTQ Description for ID: {id_str}"))?;
+        ptr = next;
+
+        let mut seq_buf: Vec<u8> = Vec::with_capacity(256);
+        loop {
+            if ptr >= raw_bytes.len() {
+                return Err(format!(
+                    "Parse Error: FASTQ record for ID {id_str} is missing its '+' separator"
+                ));
+            }
+            let (seq_line, seq_next) = take_line(raw_bytes, ptr);
+            if seq_line.first() == Some(&b'+') {
+                break;
+            }
+            seq_buf.extend_from_slice(seq_line);
+            ptr = seq_next;
+        }
+
+        // Skip the '+' separator line itself (optionally repeating the id).
+        let (_, sep_next) = take_line(raw_bytes, ptr);
+        ptr = sep_next;
+
+        let seq_len = seq_buf.len();
+        let mut qual_buf: Vec<u8> = Vec::with_capacity(seq_len);
+        while qual_buf.len() < seq_len {
+            if ptr >= raw_bytes.len() {
+                return Err(format!(
+                    "Parse Error: FASTQ record for ID {id_str} has quality length {} but sequence length {seq_len}",
+                    qual_buf.len()
+                ));
+            }
+            let (qual_line, qual_next) = take_line(raw_bytes, ptr);
+            qual_buf.extend_from_slice(qual_line);
+            ptr = qual_next;
+        }
+
+        if qual_buf.len() != seq_len {
+            return Err(format!(
+                "Parse Error: FASTQ record for ID {id_str} has quality length {} but sequence length {seq_len}",
+                qual_buf.len()
+            ));
+        }
+
+        let seq_str = str::from_utf8(&seq_buf)
+            .map_err(|_| format!("Invalid UTF-8 in sequence data for ID: {id_str}"))?;
+        let qual_str = str::from_utf8(&qual_buf)
+            .map_err(|_| format!("Invalid UTF-8 in quality data for ID: {id_str}"))?;
+
+        let stored_id = arena.alloc_str(id_str);
+        let stored_desc = arena.alloc_str(desc_str);
+        // Allocated back-to-back so `data`/`qual` stay contiguous in the
+        // arena, mirroring the id/desc/data locality `parse_fasta_into_arena`
+        // relies on (see `test_memory_layout_locality`).
+        let stored_seq = arena.alloc_str(seq_str);
+        let stored_qual = arena.alloc_str(qual_str);
+
+        entries.push(FastqEntry {
+            id: stored_id,
+            desc: stored_desc,
+            data: stored_seq,
+            qual: stored_qual,
+        });
+    }
+
+    Ok(entries.into_boxed_slice())
+}
+
+/// A FASTA record whose sequence is kept as raw bytes rather than validated
+/// UTF-8, returned by [`parse_fasta_bytes_into_arena`].
+#[derive(Debug, Clone)]
+pub struct FastaEntryBytes<'a> {
+    pub id: &'a str,
+    pub desc: &'a str,
+    pub data: &'a [u8],
+}
+
+/// Byte-preserving sibling of [`parse_fasta_into_arena`]: the header id and
+/// description still have to be valid UTF-8 to become `&str`, but the
+/// sequence body is copied into the arena untouched, with no UTF-8 check at
+/// all. Use this over [`parse_fasta_into_arena`] for inputs that legitimately
+/// aren't valid UTF-8 sequence data -- IUPAC ambiguity codes and soft-masked
+/// lowercase are fine either way, but some sources (protein FASTA with stray
+/// non-ASCII annotation characters, corrupted downloads) aren't, and
+/// `parse_fasta_into_arena` would reject those outright.
+#[cfg(feature = "std")]
+pub fn parse_fasta_bytes_into_arena<'a>(
+    arena: &'a BumpArena,
+    raw_bytes: &[u8],
+) -> Result<Box<[FastaEntryBytes<'a>]>, String> {
+    let mut entries = Vec::new();
+
+    let mut current_header: Option<(&'a str, &'a str)> = None;
+    let mut current_seq_buf: Vec<u8> = Vec::with_capacity(4096);
+
+    let mut ptr = 0;
+    while ptr < raw_bytes.len() {
+        let (line_content, next) = take_line(raw_bytes, ptr);
+
+        if line_content.is_empty() {
+            ptr = next;
+            continue;
+        }
+
+        if line_content[0] == b'>' {
+            if let Some((stored_id, stored_desc)) = current_header {
+                let stored_seq = arena.alloc_slice(&current_seq_buf);
+                entries.push(FastaEntryBytes {
+                    id: stored_id,
+                    desc: stored_desc,
+                    data: stored_seq,
+                });
+            }
+
+            let header_text = &line_content[1..]; // Skip '>'
+
+            let space_pos = header_text.iter().position(|&b| b == b' ');
+            let (raw_id, raw_desc) = match space_pos {
+                Some(p) => (&header_text[..p], &header_text[p + 1..]),
+                None => (header_text, &[] as &[u8]),
+            };
+
+            let id_str = str::from_utf8(raw_id)
+                .map_err(|_| "Invalid UTF-8 in FASTA Header ID".to_string())?;
+            let desc_str = str::from_utf8(raw_desc)
+                .map_err(|_| format!("Invalid UTF-8 in FASTA Description for ID: {}", id_str))?;
+
+            let stored_id = arena.alloc_str(id_str);
+            let stored_desc = arena.alloc_str(desc_str);
+
+            current_header = Some((stored_id, stored_desc));
+            current_seq_buf.clear();
+        } else {
+            if current_header.is_none() {
+                return Err("Parse Error: Found sequence data before the first header (line starting with >)".to_string());
+            }
+
+            current_seq_buf.extend_from_slice(line_content);
+        }
+
+        ptr = next;
+    }
+
+    if let Some((stored_id, stored_desc)) = current_header {
+        let stored_seq = arena.alloc_slice(&current_seq_buf);
+        entries.push(FastaEntryBytes {
+            id: stored_id,
+            desc: stored_desc,
+            data: stored_seq,
+        });
+    }
+
+    Ok(entries.into_boxed_slice())
+}
+
+/// Compression wrapper detected from a reader's leading magic bytes, the way
+/// downstream tools sniff input rather than trusting a file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Compression {
+    None,
+    Gzip,
+    Bgzf,
+    Zstd,
+}
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+// BGZF's FEXTRA carries a two-byte "BC" subfield identifier at this offset
+// (after the 10-byte gzip header and 2-byte XLEN), marking it as block-gzip
+// rather than a plain single-member gzip stream.
+const BGZF_EXTRA_SUBFIELD: [u8; 2] = [b'B', b'C'];
+const GZIP_FLG_FEXTRA: u8 = 0x04;
+
+fn sniff_compression(header: &[u8]) -> Compression {
+    if header.starts_with(&ZSTD_MAGIC) {
+        return Compression::Zstd;
+    }
+    if header.len() >= 2 && header[..2] == GZIP_MAGIC {
+        let has_fextra = header.len() > 3 && header[3] & GZIP_FLG_FEXTRA != 0;
+        if has_fextra && header.len() >= 14 && header[12..14] == BGZF_EXTRA_SUBFIELD {
+            return Compression::Bgzf;
+        }
+        return Compression::Gzip;
+    }
+    Compression::None
+}
+
+/// Transparently decompresses `reader` -- gzip, bgzf, zstd, or plain bytes,
+/// auto-detected from the leading magic bytes -- and parses the result as
+/// FASTA. The decompressed bytes are copied into `arena` first, so the
+/// returned entries stay valid for the arena's lifetime like every other
+/// parse in this module.
+#[cfg(feature = "std")]
+pub fn parse_fasta_auto<'a>(
+    arena: &'a BumpArena,
+    reader: impl std::io::Read,
+) -> Result<Box<[FastaEntry<'a>]>, String> {
+    use std::io::{BufRead, BufReader, Read};
+
+    let mut buffered = BufReader::new(reader);
+    let header = buffered
+        .fill_buf()
+        .map_err(|e| format!("Failed to read input header: {e}"))?;
+    let compression = sniff_compression(header);
+
+    let mut decompressed = std::vec::Vec::new();
+    match compression {
+        Compression::None => {
+            buffered
+                .read_to_end(&mut decompressed)
+                .map_err(|e| format!("Failed to read input: {e}"))?;
+        }
+        Compression::Gzip | Compression::Bgzf => {
+            // BGZF is a concatenation of independently-compressed gzip
+            // members; a multi-member gzip decoder reads straight through
+            // both it and a plain single-member gzip stream the same way.
+            flate2::read::MultiGzDecoder::new(buffered)
+                .read_to_end(&mut decompressed)
+                .map_err(|e| format!("Failed to decompress gzip/bgzf input: {e}"))?;
+        }
+        Compression::Zstd => {
+            zstd::stream::read::Decoder::new(buffered)
+                .map_err(|e| format!("Failed to start zstd decoder: {e}"))?
+                .read_to_end(&mut decompressed)
+                .map_err(|e| format!("Failed to decompress zstd input: {e}"))?;
+        }
+    }
+
+    let raw_bytes = arena.alloc_slice(&decompressed);
+    parse_fasta_into_arena(arena, raw_bytes)
+}
+
+/// A FASTA record whose sequence may borrow directly out of the input it was
+/// parsed from, returned by [`parse_fasta_borrowed`]. `data` is
+/// [`Cow::Borrowed`] for the common single-line-sequence case and only
+/// [`Cow::Owned`] when the lines had to be joined.
+#[derive(Debug, Clone)]
+pub struct FastaEntryBorrowed<'a> {
+    pub id: &'a str,
+    pub desc: &'a str,
+    pub data: Cow<'a, str>,
+}
+
+fn finish_borrowed_entry<'a>(
+    id: &'a str,
+    desc: &'a str,
+    seq_lines: &[&'a str],
+) -> FastaEntryBorrowed<'a> {
+    let data = match seq_lines {
+        [] => Cow::Borrowed(""),
+        [single] => Cow::Borrowed(*single),
+        many => Cow::Owned(many.concat()),
+    };
+    FastaEntryBorrowed { id, desc, data }
+}
+
+/// Zero-copy sibling of [`parse_fasta_into_arena`]: needs no arena at all,
+/// and when a record's sequence occupies exactly one line -- no interior
+/// newline to strip -- its entry borrows that line straight out of
+/// `raw_bytes` instead of copying it through a buffer first, following
+/// entab's approach of returning a `Cow`. A sequence wrapped across multiple
+/// lines still needs its pieces joined, so that case falls back to an owned,
+/// heap-allocated `String`. Requires `raw_bytes` to already live as long as
+/// `'a`, since nothing is copied into an arena to guarantee that.
+pub fn parse_fasta_borrowed<'a>(
+    raw_bytes: &'a [u8],
+) -> Result<Box<[FastaEntryBorrowed<'a>]>, String> {
+    let mut entries = Vec::new();
+    let mut current_header: Option<(&'a str, &'a str)> = None;
+    let mut seq_lines: Vec<&'a str> = Vec::new();
+
+    let mut ptr = 0;
+    while ptr < raw_bytes.len() {
+        let (line, next) = take_line(raw_bytes, ptr);
+
+        if line.is_empty() {
+            ptr = next;
+            continue;
+        }
+
+        if line[0] == b'>' {
+            if let Some((stored_id, stored_desc)) = current_header.take() {
+                entries.push(finish_borrowed_entry(stored_id, stored_desc, &seq_lines));
+            }
+
+            let header_text = &line[1..];
+            let space_pos = header_text.iter().position(|&b| b == b' ');
+            let (raw_id, raw_desc) = match space_pos {
+                Some(p) => (&header_text[..p], &header_text[p + 1..]),
+                None => (header_text, &[] as &[u8]),
+            };
+
+            let id_str = str::from_utf8(raw_id)
+                .map_err(|_| "Invalid UTF-8 in FASTA Header ID".to_string())?;
+            let desc_str = str::from_utf8(raw_desc)
+                .map_err(|_| format!("Invalid UTF-8 in FASTA Description for ID: {id_str}"))?;
+
+            current_header = Some((id_str, desc_str));
+            seq_lines.clear();
+        } else {
+            let Some((stored_id, _)) = current_header else {
+                return Err("Parse Error: Found sequence data before the first header (line starting with >)".to_string());
+            };
+
+            let seq_line = str::from_utf8(line)
+                .map_err(|_| format!("Invalid UTF-8 in sequence data for ID: {stored_id}"))?;
+            seq_lines.push(seq_line);
+        }
+
+        ptr = next;
+    }
+
+    if let Some((stored_id, stored_desc)) = current_header {
+        entries.push(finish_borrowed_entry(stored_id, stored_desc, &seq_lines));
+    }
+
+    Ok(entries.into_boxed_slice())
+}
+
+/// A faidx-style random-access index over a parsed FASTA file: every
+/// record's id resolves in O(1) to its [`FastaEntry`], and
+/// [`FastaIndex::fetch`] pulls out an arbitrary subrange of a record's
+/// sequence without rescanning the file, mirroring samtools' `.fai`. Unlike
+/// `.fai`, there's no per-record line-wrapping shape to record here --
+/// [`parse_fasta_into_arena`] already flattens wrapped sequences into one
+/// contiguous arena string, so a `(start, end)` region is always a plain
+/// substring slice.
+pub struct FastaIndex<'a> {
+    entries: Box<[FastaEntry<'a>]>,
+    by_id: HashMap<&'a str, usize>,
+}
+
+/// Parses `raw_bytes` into the arena, like [`parse_fasta_into_arena`], and
+/// additionally builds a [`FastaIndex`] over the result keyed by record id.
+#[cfg(feature = "std")]
+pub fn build_fasta_index<'a>(
+    arena: &'a BumpArena,
+    raw_bytes: &[u8],
+) -> Result<FastaIndex<'a>, String> {
+    let entries = parse_fasta_into_arena(arena, raw_bytes)?;
+    let by_id = entries.iter().enumerate().map(|(i, e)| (e.id, i)).collect();
+    Ok(FastaIndex { entries, by_id })
+}
+
+impl<'a> FastaIndex<'a> {
+    /// Look up a record by id.
+    pub fn get(&self, id: &str) -> Option<&FastaEntry<'a>> {
+        let &entry_index = self.by_id.get(id)?;
+        Some(&self.entries[entry_index])
+    }
+
+    /// Extract the `[start, end)` subrange (0-based, end-exclusive, in bytes
+    /// of the sequence) of `id`'s sequence. Returns `None` for an unknown id,
+    /// an inverted/out-of-range `(start, end)`, or a bound that falls inside
+    /// a multi-byte UTF-8 character.
+    ///
+    /// `arena` must be the same arena the index was built against -- that's
+    /// what every returned entry's `data` is already allocated in, so
+    /// `fetch` only needs it to assert that invariant, not to allocate.
+    pub fn fetch(&self, arena: &'a BumpArena, id: &str, start: usize, end: usize) -> Option<&'a str> {
+        let entry = self.get(id)?;
+        if start > end
+            || end > entry.data.len()
+            || !entry.data.is_char_boundary(start)
+            || !entry.data.is_char_boundary(end)
+        {
+            return None;
+        }
+        let data_ptr = entry.data.as_ptr() as usize;
+        let arena_start = arena.start() as usize;
+        debug_assert!(
+            data_ptr >= arena_start && data_ptr < arena_start + arena.capacity(),
+            "FastaIndex::fetch called with a different arena than the index was built against"
+        );
+        Some(&entry.data[start..end])
+    }
+}
+
+/// Writes `entries` back out in FASTA format: a `>{id} {desc}` header (the
+/// space and `desc` are both omitted when `desc` is empty) followed by the
+/// sequence hard-wrapped at `line_width` characters per line. `line_width`
+/// of `0` writes the whole sequence on one line.
+#[cfg(feature = "std")]
+pub fn write_fasta(
+    entries: &[FastaEntry],
+    out: &mut impl std::io::Write,
+    line_width: usize,
+) -> std::io::Result<()> {
+    for entry in entries {
+        if entry.desc.is_empty() {
+            writeln!(out, ">{}", entry.id)?;
+        } else {
+            writeln!(out, ">{} {}", entry.id, entry.desc)?;
+        }
+
+        if line_width == 0 {
+            writeln!(out, "{}", entry.data)?;
+            continue;
+        }
+
+        let bytes = entry.data.as_bytes();
+        for chunk in bytes.chunks(line_width) {
+            out.write_all(chunk)?;
+            out.write_all(b"\n")?;
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -176,6 +671,227 @@ C";
         // "B" is 1 byte + alignment padding
         println!("ID: {:x}, Desc: {:x}, Data: {:x}", p_id, p_desc, p_data);
     }
+
+    #[test]
+    fn test_parse_valid_fastq() {
+        let arena = BumpArena::new(4096);
+        let raw = b"@seq1 Human Gene\nATGC\n+\nIIII\n@seq2\nGGCC\n+seq2\nJJJJ";
+
+        let entries = parse_fastq_into_arena(&arena, raw).unwrap();
+        assert_eq!(entries.len(), 2);
+
+        assert_eq!(entries[0].id, "seq1");
+        assert_eq!(entries[0].desc, "Human Gene");
+        assert_eq!(entries[0].data, "ATGC");
+        assert_eq!(entries[0].qual, "IIII");
+
+        assert_eq!(entries[1].id, "seq2");
+        assert_eq!(entries[1].desc, "");
+        assert_eq!(entries[1].data, "GGCC");
+        assert_eq!(entries[1].qual, "JJJJ");
+    }
+
+    #[test]
+    fn test_parse_fastq_multiline_with_at_in_quality() {
+        let arena = BumpArena::new(4096);
+        // Quality line starts with '@', which must not be mistaken for the
+        // next record's header: the parser knows to keep reading quality
+        // bytes until it has matched the (also multi-line) sequence's length.
+        let raw = b"@seq1\nATGC\nATGC\n+\n@III\nIIII";
+
+        let entries = parse_fastq_into_arena(&arena, raw).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].data, "ATGCATGC");
+        assert_eq!(entries[0].qual, "@IIIIIII");
+    }
+
+    #[test]
+    fn test_error_fastq_length_mismatch() {
+        let arena = BumpArena::new(1024);
+        let raw = b"@seq1\nATGC\n+\nIII";
+
+        let result = parse_fastq_into_arena(&arena, raw);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .contains("quality length 3 but sequence length 4"));
+    }
+
+    #[test]
+    fn test_fastq_memory_layout_locality() {
+        let arena = BumpArena::new(4096);
+        let raw = b"@A\nC\n+\nI";
+        let entries = parse_fastq_into_arena(&arena, raw).unwrap();
+        let e = &entries[0];
+
+        let p_data = e.data.as_ptr() as usize;
+        let p_qual = e.qual.as_ptr() as usize;
+        assert!(p_data < p_qual, "data and qual should be allocated back-to-back");
+    }
+
+    #[test]
+    fn test_sniff_compression_variants() {
+        assert_eq!(sniff_compression(&[0x28, 0xb5, 0x2f, 0xfd, 0x00]), Compression::Zstd);
+        assert_eq!(sniff_compression(&[0x1f, 0x8b, 0x08, 0x00]), Compression::Gzip);
+        assert_eq!(sniff_compression(b">seq1\nATGC"), Compression::None);
+
+        // A minimal BGZF header: gzip magic, FLG with FEXTRA set, XLEN=6,
+        // then a "BC" subfield.
+        let mut bgzf_header = vec![0x1f, 0x8b, 0x08, GZIP_FLG_FEXTRA, 0, 0, 0, 0, 0, 0xff];
+        bgzf_header.extend_from_slice(&6u16.to_le_bytes());
+        bgzf_header.extend_from_slice(b"BC");
+        assert_eq!(sniff_compression(&bgzf_header), Compression::Bgzf);
+    }
+
+    #[test]
+    fn test_parse_fasta_auto_plain_passthrough() {
+        let arena = BumpArena::new(4096);
+        let raw = b">seq1 desc\nATGC";
+
+        let entries = parse_fasta_auto(&arena, &raw[..]).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, "seq1");
+        assert_eq!(entries[0].data, "ATGC");
+    }
+
+    #[test]
+    fn test_parse_fasta_auto_gzip() {
+        use std::io::Write;
+
+        let raw = b">seq1 desc\nATGC\n>seq2\nGGCC";
+        let mut encoder =
+            flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(raw).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let arena = BumpArena::new(4096);
+        let entries = parse_fasta_auto(&arena, &compressed[..]).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].data, "ATGC");
+        assert_eq!(entries[1].data, "GGCC");
+    }
+
+    #[test]
+    fn test_parse_fasta_borrowed_single_line_is_zero_copy() {
+        let raw = b">seq1 Human Gene\nATGC\n>seq2\nGGCC";
+
+        let entries = parse_fasta_borrowed(raw).unwrap();
+        assert_eq!(entries.len(), 2);
+
+        assert_eq!(entries[0].id, "seq1");
+        assert_eq!(entries[0].desc, "Human Gene");
+        assert_eq!(entries[0].data, "ATGC");
+        assert!(matches!(entries[0].data, Cow::Borrowed(_)));
+        // The borrowed sequence must point inside `raw`, not a copy of it.
+        let data_ptr = entries[0].data.as_ref().as_ptr();
+        assert!(data_ptr >= raw.as_ptr() && data_ptr < unsafe { raw.as_ptr().add(raw.len()) });
+
+        assert_eq!(entries[1].data, "GGCC");
+    }
+
+    #[test]
+    fn test_parse_fasta_borrowed_multiline_is_owned() {
+        let raw = b">seq1\nATGC\nATGC";
+
+        let entries = parse_fasta_borrowed(raw).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].data, "ATGCATGC");
+        assert!(matches!(entries[0].data, Cow::Owned(_)));
+    }
+
+    #[test]
+    fn test_fasta_index_get_and_fetch() {
+        let arena = BumpArena::new(4096);
+        let raw = b">seq1 Human Gene\nATGC\nATGC\n>seq2\nGGCC";
+
+        let index = build_fasta_index(&arena, raw).unwrap();
+
+        let entry = index.get("seq1").unwrap();
+        assert_eq!(entry.desc, "Human Gene");
+        assert_eq!(entry.data, "ATGCATGC");
+
+        assert_eq!(index.fetch(&arena, "seq1", 2, 6).unwrap(), "GCAT");
+        assert_eq!(index.fetch(&arena, "seq2", 0, 4).unwrap(), "GGCC");
+    }
+
+    #[test]
+    fn test_fasta_index_missing_id_and_out_of_range() {
+        let arena = BumpArena::new(4096);
+        let raw = b">seq1\nATGC";
+
+        let index = build_fasta_index(&arena, raw).unwrap();
+
+        assert!(index.get("seq2").is_none());
+        assert!(index.fetch(&arena, "seq2", 0, 1).is_none());
+        assert!(index.fetch(&arena, "seq1", 2, 1).is_none());
+        assert!(index.fetch(&arena, "seq1", 0, 100).is_none());
+    }
+
+    #[test]
+    fn test_write_fasta_round_trip_at_width_four() {
+        let arena = BumpArena::new(4096);
+        let raw = b">seq1 Human Gene
+ATGC
+ATGC
+>seq2
+GGCC";
+
+        let entries = parse_fasta_into_arena(&arena, raw).unwrap();
+
+        let mut out = Vec::new();
+        write_fasta(&entries, &mut out, 4).unwrap();
+
+        assert_eq!(
+            out,
+            b">seq1 Human Gene\nATGC\nATGC\n>seq2\nGGCC\n".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_write_fasta_empty_desc_and_unwrapped() {
+        let arena = BumpArena::new(4096);
+        let raw = b">seq1\nATGCATGC";
+
+        let entries = parse_fasta_into_arena(&arena, raw).unwrap();
+
+        let mut out = Vec::new();
+        write_fasta(&entries, &mut out, 0).unwrap();
+
+        assert_eq!(out, b">seq1\nATGCATGC\n".to_vec());
+    }
+
+    #[test]
+    fn test_parse_fasta_bytes_valid() {
+        let arena = BumpArena::new(4096);
+        let raw = b">seq1 Human Gene
+ATGC
+ATGC
+>seq2
+GGCC";
+
+        let entries = parse_fasta_bytes_into_arena(&arena, raw).unwrap();
+        assert_eq!(entries.len(), 2);
+
+        assert_eq!(entries[0].id, "seq1");
+        assert_eq!(entries[0].desc, "Human Gene");
+        assert_eq!(entries[0].data, b"ATGCATGC");
+
+        assert_eq!(entries[1].id, "seq2");
+        assert_eq!(entries[1].desc, "");
+        assert_eq!(entries[1].data, b"GGCC");
+    }
+
+    #[test]
+    fn test_parse_fasta_bytes_preserves_non_utf8_sequence() {
+        let arena = BumpArena::new(4096);
+        // `parse_fasta_into_arena` rejects this input (see
+        // `test_error_invalid_utf8`); the byte-preserving parser must not.
+        let raw = b">seq1\nATG\xFFC";
+
+        let entries = parse_fasta_bytes_into_arena(&arena, raw).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].data, b"ATG\xFFC");
+    }
 }
 
 #[cfg(feature = "extensive_tests")]
@@ -258,4 +974,40 @@ mod test_full {
             "No entries parsed! Is the data folder empty?"
         );
     }
+
+    #[test]
+    fn test_line_scan_scalar_vs_memchr_benchmark() {
+        // A synthetic multi-sequence FASTA large enough for the scan cost to
+        // dominate over per-iteration overhead.
+        let mut raw = Vec::with_capacity(8 * 1024 * 1024);
+        for i in 0..20_000 {
+            raw.extend_from_slice(format!(">seq{i} synthetic\n").as_bytes());
+            raw.extend_from_slice(b"ACGTACGTACGTACGTACGTACGTACGTACGTACGTACGT\n");
+        }
+
+        let scan_with = |line_fn: fn(&[u8], usize) -> (&[u8], usize)| -> (usize, std::time::Duration) {
+            let start = Instant::now();
+            let mut lines = 0usize;
+            let mut ptr = 0;
+            while ptr < raw.len() {
+                let (_, next) = line_fn(&raw, ptr);
+                lines += 1;
+                ptr = next;
+            }
+            (lines, start.elapsed())
+        };
+
+        let (scalar_lines, scalar_duration) = scan_with(take_line_scalar);
+        let (memchr_lines, memchr_duration) = scan_with(take_line);
+
+        assert_eq!(
+            scalar_lines, memchr_lines,
+            "scalar and memchr scanners must agree on line count"
+        );
+
+        println!(
+            "\n--- [Extensive Test] Line scan: scalar {:.2?} vs memchr {:.2?} over {} lines ---",
+            scalar_duration, memchr_duration, scalar_lines
+        );
+    }
 }
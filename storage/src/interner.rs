@@ -0,0 +1,89 @@
+use core::cell::RefCell;
+use core::fmt;
+
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+
+use crate::BumpArena;
+
+/// A small integer handle standing in for an interned string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symbol(pub u32);
+
+/// Atom table that collapses repeated strings down to small integer ids. Each
+/// distinct string is copied into the backing arena exactly once on first
+/// sight; repeats resolve to the existing id, so files full of shared record
+/// identifiers (organism tags, repeated descriptions) cost one slice apiece.
+#[derive(Clone)]
+pub struct Interner<'a> {
+    arena: &'a BumpArena,
+    table: RefCell<HashMap<&'a str, u32>>,
+    index: RefCell<Vec<&'a str>>,
+    interned: RefCell<u64>,
+}
+
+impl fmt::Debug for Interner<'_> {
+    // `BumpArena` carries no `Debug` impl of its own, so summarize it by size
+    // rather than deriving through it.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interner")
+            .field("symbols", &self.index.borrow().len())
+            .field("interned", &*self.interned.borrow())
+            .finish()
+    }
+}
+
+impl<'a> Interner<'a> {
+    pub fn new(arena: &'a BumpArena) -> Self {
+        Self {
+            arena,
+            table: RefCell::new(HashMap::new()),
+            index: RefCell::new(Vec::new()),
+            interned: RefCell::new(0),
+        }
+    }
+
+    /// Intern `s`, returning its existing [`Symbol`] or copying it into the
+    /// arena and assigning a fresh id on first sight.
+    pub fn intern(&self, s: &str) -> Symbol {
+        *self.interned.borrow_mut() += 1;
+        if let Some(&id) = self.table.borrow().get(s) {
+            return Symbol(id);
+        }
+        let stored: &'a str = self.arena.alloc_str(s);
+        let mut index = self.index.borrow_mut();
+        let id = index.len() as u32;
+        index.push(stored);
+        self.table.borrow_mut().insert(stored, id);
+        Symbol(id)
+    }
+
+    /// Resolve a previously returned [`Symbol`] back to its string.
+    pub fn resolve(&self, sym: Symbol) -> &'a str {
+        self.index.borrow()[sym.0 as usize]
+    }
+
+    /// Number of distinct strings held.
+    pub fn len(&self) -> usize {
+        self.index.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.borrow().is_empty()
+    }
+
+    /// Total number of `intern` calls, including repeats.
+    pub fn interned_count(&self) -> u64 {
+        *self.interned.borrow()
+    }
+
+    /// Fraction of `intern` calls that hit an already-stored string. Zero when
+    /// nothing has been interned yet.
+    pub fn dedup_ratio(&self) -> f64 {
+        let total = *self.interned.borrow();
+        if total == 0 {
+            return 0.0;
+        }
+        1.0 - (self.len() as f64 / total as f64)
+    }
+}
@@ -1,15 +1,29 @@
 #![allow(unused)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 pub mod dataset;
 pub mod fasta;
+pub mod interner;
+pub mod sbt;
+
+// File loading and the OS-backed arena mapping are only available with `std`.
+#[cfg(feature = "std")]
+pub mod delimited;
+#[cfg(feature = "std")]
 mod system;
 
-use std::{
+use alloc::vec::Vec;
+use core::{
     alloc::Layout,
     cell::UnsafeCell,
     marker::PhantomData,
+    mem,
     ptr::{self, NonNull},
 };
 
+#[cfg(feature = "std")]
 use system::{map_memory, unmap_memory};
 
 struct BumpArenaInner {
@@ -17,6 +31,29 @@ struct BumpArenaInner {
     ptr: *mut u8,
     end: *mut u8,
     total_size: usize,
+    // Whether the arena owns an OS mapping that must be released on drop.
+    // Slice-backed arenas borrow their storage and leave it untouched.
+    owns_mapping: bool,
+    // Deferred destructors for allocations whose `T: !Copy` needs dropping.
+    // Empty for the common `Copy`/byte-slice path, so those pay no bookkeeping.
+    drops: Vec<DropEntry>,
+}
+
+/// One deferred destructor: the allocation's base pointer, element count, and a
+/// monomorphized thunk that drops the stored `T` (or `[T]`) in place. Entries
+/// are recorded only for types where [`mem::needs_drop`] holds.
+struct DropEntry {
+    ptr: *mut u8,
+    len: usize,
+    drop_fn: unsafe fn(*mut u8, usize),
+}
+
+/// Type-erased destructor for `len` consecutive `T`s starting at `ptr`.
+unsafe fn drop_thunk<T>(ptr: *mut u8, len: usize) {
+    let slice = ptr::slice_from_raw_parts_mut(ptr as *mut T, len);
+    // SAFETY: `ptr` points at `len` initialized `T`s written by the arena and
+    // not touched since; this runs exactly once from `Drop`/`reset`.
+    unsafe { ptr::drop_in_place(slice) };
 }
 
 pub struct BumpArena {
@@ -24,7 +61,14 @@ pub struct BumpArena {
     _marker: PhantomData<*mut u8>,
 }
 
+/// Opaque bump-pointer marker captured by [`BumpArena::checkpoint`] and
+/// consumed by [`BumpArena::rewind`]/[`BumpArena::scope`]. Only meaningful for
+/// the arena that produced it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint(*mut u8);
+
 impl BumpArena {
+    #[cfg(feature = "std")]
     pub fn new(size: usize) -> Self {
         assert!(size > 0, "Size must be > 0");
         let start_ptr = map_memory(size).expect("BumpArena: mmap failed");
@@ -37,6 +81,33 @@ impl BumpArena {
                 ptr: raw_start,
                 end: end_ptr,
                 total_size: size,
+                owns_mapping: true,
+                drops: Vec::new(),
+            }),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Build an arena backed by a caller-supplied buffer. This needs no global
+    /// allocator or OS mapping, so it is the constructor to use in `no_std`
+    /// builds; the buffer is borrowed for the arena's lifetime and left intact
+    /// on drop.
+    pub fn from_slice(buf: &mut [u8]) -> Self {
+        let size = buf.len();
+        assert!(size > 0, "Size must be > 0");
+        let raw_start = buf.as_mut_ptr();
+        // SAFETY: `buf` is a valid, non-null slice of length `size`.
+        let start_ptr = unsafe { NonNull::new_unchecked(raw_start) };
+        let end_ptr = unsafe { raw_start.add(size) };
+
+        Self {
+            inner: UnsafeCell::new(BumpArenaInner {
+                start: start_ptr,
+                ptr: raw_start,
+                end: end_ptr,
+                total_size: size,
+                owns_mapping: false,
+                drops: Vec::new(),
             }),
             _marker: PhantomData,
         }
@@ -81,39 +152,221 @@ impl BumpArena {
         }
     }
 
-    /// Allocate a value.
+    /// Allocate a value. If `T` needs dropping, its destructor is recorded and
+    /// run when the arena is dropped or reset; `Copy`/no-drop types skip that
+    /// bookkeeping entirely.
     #[inline(always)]
     pub fn alloc<T>(&self, value: T) -> &mut T {
         let layout = Layout::new::<T>();
         let ptr = self.alloc_layout(layout) as *mut T;
         unsafe {
             ptr::write(ptr, value);
+            if mem::needs_drop::<T>() {
+                self.register_drop::<T>(ptr as *mut u8, 1);
+            }
             &mut *ptr
         }
     }
 
+    /// Reserve space for a `T`, then initialize it in place from `f`, mirroring
+    /// the reserve-then-init shape of [`try_alloc_with`](Self::try_alloc_with).
+    /// Useful when `T` is built from data that itself needs to outlive the
+    /// call (e.g. a `String`/`Vec<T>` assembled from arena-borrowed slices)
+    /// without materializing it on the stack first.
+    #[inline(always)]
+    pub fn alloc_with<T>(&self, f: impl FnOnce() -> T) -> &mut T {
+        let layout = Layout::new::<T>();
+        let ptr = self.alloc_layout(layout) as *mut T;
+        unsafe {
+            ptr::write(ptr, f());
+            if mem::needs_drop::<T>() {
+                self.register_drop::<T>(ptr as *mut u8, 1);
+            }
+            &mut *ptr
+        }
+    }
+
+    /// Record a deferred destructor for `len` `T`s at `ptr`.
+    #[inline]
+    fn register_drop<T>(&self, ptr: *mut u8, len: usize) {
+        let inner = unsafe { self.get_inner_mut() };
+        inner.drops.push(DropEntry {
+            ptr,
+            len,
+            drop_fn: drop_thunk::<T>,
+        });
+    }
+
+    /// Move the elements yielded by `iter` into the arena as a contiguous slice.
+    /// Unlike [`alloc_slice`](Self::alloc_slice), which copies from an existing
+    /// `Copy` slice, this consumes any `T` and registers its destructors when
+    /// `T` needs dropping, so non-`Copy` structures can live in the arena.
+    pub fn alloc_slice_iter<T, I>(&self, iter: I) -> &mut [T]
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let iter = iter.into_iter();
+        let len = iter.len();
+        let layout = Layout::array::<T>(len).expect("BumpArena: slice layout overflow");
+        let dst = self.alloc_layout(layout) as *mut T;
+        unsafe {
+            for (i, value) in iter.enumerate() {
+                ptr::write(dst.add(i), value);
+            }
+            if mem::needs_drop::<T>() {
+                self.register_drop::<T>(dst as *mut u8, len);
+            }
+            core::slice::from_raw_parts_mut(dst, len)
+        }
+    }
+
+    /// Reserve `len` uninitialized, byte-aligned bytes in the arena and hand
+    /// back the raw span. Unlike [`alloc_slice`](Self::alloc_slice), nothing is
+    /// copied in, so a caller that can fill the bytes in place (e.g. reading a
+    /// file straight into arena memory) avoids the intermediate heap buffer.
+    ///
+    /// # Safety
+    /// The returned slice's contents are unspecified until the caller
+    /// initializes them; reading from it before writing is undefined
+    /// behavior wherever the read is interpreted as anything but raw bytes
+    /// (e.g. `str::from_utf8` requires the bytes to actually be valid UTF-8).
+    pub fn alloc_uninit_bytes(&self, len: usize) -> &mut [u8] {
+        let layout = Layout::from_size_align(len, 1).expect("BumpArena: byte layout overflow");
+        let ptr = self.alloc_layout(layout);
+        unsafe { core::slice::from_raw_parts_mut(ptr, len) }
+    }
+
     /// Clone a slice into the arena.
     pub fn alloc_slice<T: Copy>(&self, src: &[T]) -> &mut [T] {
         let layout = Layout::for_value(src);
         let dst_ptr = self.alloc_layout(layout) as *mut T;
         unsafe {
             ptr::copy_nonoverlapping(src.as_ptr(), dst_ptr, src.len());
-            std::slice::from_raw_parts_mut(dst_ptr, src.len())
+            core::slice::from_raw_parts_mut(dst_ptr, src.len())
         }
     }
 
     /// Clone a str into the arena.
     pub fn alloc_str(&self, src: &str) -> &mut str {
         let buf = self.alloc_slice(src.as_bytes());
-        unsafe { std::str::from_utf8_unchecked_mut(buf) }
+        unsafe { core::str::from_utf8_unchecked_mut(buf) }
+    }
+
+    /// Fallible sibling of [`alloc_layout`](Self::alloc_layout): returns `None`
+    /// when the arena is exhausted instead of panicking, so callers on a hot
+    /// path can treat genuine out-of-memory as a recoverable condition.
+    pub fn try_alloc_layout(&self, layout: Layout) -> Option<*mut u8> {
+        let inner = unsafe { self.get_inner_mut() };
+
+        let align = layout.align();
+        let size = layout.size();
+        let offset = inner.ptr.align_offset(align);
+
+        if offset == usize::MAX {
+            return None;
+        }
+
+        unsafe {
+            let aligned_ptr = inner.ptr.add(offset);
+            let new_ptr = aligned_ptr.add(size);
+
+            if new_ptr > inner.end {
+                return None;
+            }
+
+            inner.ptr = new_ptr;
+            Some(aligned_ptr)
+        }
+    }
+
+    /// Reserve `len` bytes and, only once the space is secured, copy the string
+    /// produced by `init` into place. Returns `None` if the arena is exhausted
+    /// rather than panicking, following the standard typed-arena pattern of
+    /// initializing in place from a closure.
+    pub fn try_alloc_with<'s, F: FnOnce() -> &'s str>(
+        &self,
+        len: usize,
+        init: F,
+    ) -> Option<&mut str> {
+        let layout = Layout::from_size_align(len, 1).ok()?;
+        let ptr = self.try_alloc_layout(layout)?;
+        let src = init();
+        debug_assert_eq!(
+            src.len(),
+            len,
+            "try_alloc_with: init produced a differently sized string"
+        );
+        unsafe {
+            ptr::copy_nonoverlapping(src.as_ptr(), ptr, len);
+            Some(core::str::from_utf8_unchecked_mut(
+                core::slice::from_raw_parts_mut(ptr, len),
+            ))
+        }
     }
 
     /// Reset.
     pub fn reset(&mut self) {
         let inner = self.inner.get_mut(); // Safe because we have &mut self
+        // Run any pending destructors before the storage is reused, then rewind.
+        for entry in inner.drops.drain(..).rev() {
+            unsafe { (entry.drop_fn)(entry.ptr, entry.len) };
+        }
         inner.ptr = inner.start.as_ptr();
     }
 
+    /// Capture the current bump pointer as an opaque, restorable marker.
+    ///
+    /// Pair with [`rewind`](Self::rewind) or [`scope`](Self::scope) to reclaim
+    /// everything allocated since, without resetting the whole arena.
+    pub fn checkpoint(&self) -> Checkpoint {
+        let inner = unsafe { self.get_inner_mut() };
+        Checkpoint(inner.ptr)
+    }
+
+    /// Rewind the arena to a `Checkpoint` captured earlier, reclaiming every
+    /// allocation made since. Destructors for those allocations run now, in
+    /// reverse registration order, before the space is handed back.
+    ///
+    /// # Safety
+    /// No reference returned by an `alloc*`/`try_alloc*` call made after `cp`
+    /// was captured may be used after this call: the memory it points at is
+    /// reused by the arena's next allocation, exactly as `reset` invalidates
+    /// every outstanding reference. `cp` must have been produced by this same
+    /// arena and not already be behind the current bump pointer.
+    pub unsafe fn rewind(&self, cp: Checkpoint) {
+        let inner = unsafe { self.get_inner_mut() };
+        assert!(
+            cp.0 >= inner.start.as_ptr() && cp.0 <= inner.ptr,
+            "BumpArena: checkpoint does not lie within [start, ptr]"
+        );
+        // `drops` is append-only in allocation order, which is also bump-pointer
+        // order, so the first entry at or past `cp` marks where to split.
+        let split = inner
+            .drops
+            .iter()
+            .position(|entry| entry.ptr as *const u8 >= cp.0 as *const u8)
+            .unwrap_or(inner.drops.len());
+        for entry in inner.drops.drain(split..).rev() {
+            unsafe { (entry.drop_fn)(entry.ptr, entry.len) };
+        }
+        inner.ptr = cp.0;
+    }
+
+    /// Run `f` with everything it allocates in `self` reclaimed afterwards,
+    /// via [`checkpoint`](Self::checkpoint)/[`rewind`](Self::rewind). Useful
+    /// to keep peak arena usage flat across a batch of otherwise-independent
+    /// scans, e.g. one `scope` per pattern in a multi-pattern search.
+    ///
+    /// `f` must not let a reference borrowed from `self` escape into `R`; the
+    /// arena memory it pointed at is reclaimed the moment this call returns.
+    pub fn scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        let cp = self.checkpoint();
+        let result = f();
+        unsafe { self.rewind(cp) };
+        result
+    }
+
     pub fn used(&self) -> usize {
         let inner = unsafe { self.get_inner_mut() };
         (inner.ptr as usize) - (inner.start.as_ptr() as usize)
@@ -138,7 +391,17 @@ impl BumpArena {
 impl Drop for BumpArena {
     fn drop(&mut self) {
         let inner = self.inner.get_mut();
-        unmap_memory(inner.start, inner.total_size);
+        // Run deferred destructors in reverse registration order, mirroring the
+        // drop order of a normal stack frame, before the backing store goes away.
+        for entry in inner.drops.drain(..).rev() {
+            unsafe { (entry.drop_fn)(entry.ptr, entry.len) };
+        }
+        // Only OS-mapped arenas release their backing store; slice-backed
+        // arenas borrow the caller's buffer and must leave it alone.
+        #[cfg(feature = "std")]
+        if inner.owns_mapping {
+            unmap_memory(inner.start, inner.total_size);
+        }
     }
 }
 
@@ -265,6 +528,17 @@ mod tests {
         assert!(stored_addr < arena_start + 1024);
     }
 
+    #[test]
+    fn test_alloc_uninit_bytes_reserves_contiguous_span() {
+        let arena = BumpArena::new(1024);
+
+        let buf = arena.alloc_uninit_bytes(16);
+        assert_eq!(buf.len(), 16);
+        buf.fill(0x42);
+        assert_eq!(arena.used(), 16);
+        assert_eq!(buf, &[0x42u8; 16]);
+    }
+
     #[test]
     fn test_alloc_slice_u64_alignment() {
         let arena = BumpArena::new(1024);
@@ -299,6 +573,97 @@ mod tests {
         assert_eq!(stored_str, "sELECT * FROM users");
     }
 
+    #[test]
+    fn test_try_alloc_with_success() {
+        let arena = BumpArena::new(64);
+        let value = "hello";
+        let stored = arena.try_alloc_with(value.len(), || value).unwrap();
+        assert_eq!(stored, "hello");
+    }
+
+    #[test]
+    fn test_try_alloc_with_oom_returns_none() {
+        let arena = BumpArena::new(4);
+        let value = "too large for this arena";
+        assert!(arena.try_alloc_with(value.len(), || value).is_none());
+    }
+
+    #[test]
+    fn test_alloc_runs_drop() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        struct Tracked(Rc<Cell<usize>>);
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let drops = Rc::new(Cell::new(0));
+        {
+            let arena = BumpArena::new(1024);
+            arena.alloc(Tracked(drops.clone()));
+            arena.alloc(Tracked(drops.clone()));
+            assert_eq!(drops.get(), 0, "destructors must not run before drop");
+        }
+        assert_eq!(drops.get(), 2, "both destructors should run when arena drops");
+    }
+
+    #[test]
+    fn test_alloc_with_initializes_and_runs_drop() {
+        let arena = BumpArena::new(1024);
+
+        let prefix = arena.alloc_str("foo");
+        let owned = arena.alloc_with(|| alloc::string::String::from(&*prefix) + "bar");
+        assert_eq!(owned, "foobar");
+
+        let drops = std::rc::Rc::new(std::cell::Cell::new(0));
+        struct Tracked(std::rc::Rc<std::cell::Cell<usize>>);
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+        arena.alloc_with(|| Tracked(drops.clone()));
+        assert_eq!(drops.get(), 0);
+        drop(arena);
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    fn test_alloc_slice_iter_runs_drop() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        struct Tracked(Rc<Cell<usize>>);
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let drops = Rc::new(Cell::new(0));
+        let mut arena = BumpArena::new(1024);
+
+        let stored = arena.alloc_slice_iter((0..3).map(|_| Tracked(drops.clone())));
+        assert_eq!(stored.len(), 3);
+        assert_eq!(drops.get(), 0);
+
+        // `reset` releases the current allocations, running their destructors.
+        arena.reset();
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn test_copy_alloc_registers_no_drops() {
+        let arena = BumpArena::new(1024);
+        arena.alloc(42u64);
+        arena.alloc_slice(&[1u32, 2, 3]);
+        let inner = unsafe { arena.get_inner_mut() };
+        assert!(inner.drops.is_empty(), "Copy types must not register drops");
+    }
+
     #[test]
     fn test_multiple_clones() {
         let arena = BumpArena::new(4096);
@@ -315,4 +680,68 @@ mod tests {
         let p3 = nums.as_ptr() as usize;
         assert_eq!(p3 - p2, 5);
     }
+
+    #[test]
+    fn test_checkpoint_rewind_reclaims_space() {
+        let arena = BumpArena::new(1024);
+
+        arena.alloc(1u64);
+        let cp = arena.checkpoint();
+        assert_eq!(arena.used(), 8);
+
+        arena.alloc(2u64);
+        arena.alloc(3u64);
+        assert_eq!(arena.used(), 24);
+
+        unsafe { arena.rewind(cp) };
+        assert_eq!(arena.used(), 8);
+
+        let ptr = arena.alloc(4u64) as *const u64;
+        assert_eq!(ptr as usize, arena.start() as usize + 8);
+    }
+
+    #[test]
+    fn test_rewind_runs_destructors_past_checkpoint() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        struct Tracked(Rc<Cell<usize>>);
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let drops = Rc::new(Cell::new(0));
+        let arena = BumpArena::new(1024);
+
+        arena.alloc(Tracked(drops.clone()));
+        let cp = arena.checkpoint();
+        arena.alloc(Tracked(drops.clone()));
+        arena.alloc(Tracked(drops.clone()));
+        assert_eq!(drops.get(), 0);
+
+        unsafe { arena.rewind(cp) };
+        assert_eq!(drops.get(), 2, "only allocations past the checkpoint drop");
+
+        drop(arena);
+        assert_eq!(drops.get(), 3, "the pre-checkpoint allocation drops with the arena");
+    }
+
+    #[test]
+    fn test_scope_rewinds_after_closure() {
+        let arena = BumpArena::new(1024);
+
+        arena.alloc(1u64);
+        assert_eq!(arena.used(), 8);
+
+        let doubled = arena.scope(|| {
+            let a = arena.alloc(10u64);
+            let b = arena.alloc(20u64);
+            *a + *b
+        });
+        assert_eq!(doubled, 30);
+
+        assert_eq!(arena.used(), 8, "scope reclaims everything it allocated");
+    }
 }
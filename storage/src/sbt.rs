@@ -0,0 +1,294 @@
+//! Sequence Bloom Tree over dataset columns.
+//!
+//! Every search backend otherwise scans every column for every pattern. The SBT
+//! gives the harness a sublinear pruning stage: each leaf is a Bloom filter of
+//! one column's k-mers, each internal node the bitwise-OR of its children, and a
+//! `%pattern%` query descends the tree discarding any subtree whose filter lacks
+//! enough of the pattern's k-mers. Bloom filters never report a false negative,
+//! so the tree may over-report candidate columns but never drops a true one.
+
+use alloc::{vec, vec::Vec};
+
+use crate::dataset::DataSet;
+use crate::BumpArena;
+
+/// A column's position in the `DataSet`.
+pub type ColumnId = usize;
+
+/// Bloom filter size in bits per leaf, and the number of hash functions. Fixed
+/// so every filter in the tree is layout-compatible for the union step.
+const BLOOM_BITS: usize = 1 << 16;
+const BLOOM_WORDS: usize = BLOOM_BITS / 64;
+const BLOOM_HASHES: usize = 4;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+fn fnv1a(data: &[u8], seed: u64) -> u64 {
+    let mut h = FNV_OFFSET ^ seed;
+    for &b in data {
+        h ^= b as u64;
+        h = h.wrapping_mul(FNV_PRIME);
+    }
+    h
+}
+
+/// Derive the `BLOOM_HASHES` bit positions of one k-mer by double hashing.
+#[inline]
+fn bit_positions(kmer: &[u8]) -> [usize; BLOOM_HASHES] {
+    let h1 = fnv1a(kmer, 0);
+    // A second, odd hash so successive probes stride the whole filter.
+    let h2 = fnv1a(kmer, 0x9e37_79b9_7f4a_7c15) | 1;
+    let mut out = [0usize; BLOOM_HASHES];
+    for (i, slot) in out.iter_mut().enumerate() {
+        let combined = h1.wrapping_add((i as u64).wrapping_mul(h2));
+        *slot = (combined % BLOOM_BITS as u64) as usize;
+    }
+    out
+}
+
+#[inline]
+fn set_bit(words: &mut [u64], pos: usize) {
+    words[pos / 64] |= 1u64 << (pos % 64);
+}
+
+#[inline]
+fn test_bit(words: &[u64], pos: usize) -> bool {
+    words[pos / 64] & (1u64 << (pos % 64)) != 0
+}
+
+struct SbtNode<'a> {
+    filter: &'a [u64],
+    // Set for leaves; `None` for internal union nodes.
+    column: Option<ColumnId>,
+    children: [Option<usize>; 2],
+}
+
+/// A balanced binary Bloom tree over a `DataSet`'s columns.
+pub struct SequenceBloomTree<'a> {
+    k: usize,
+    nodes: Vec<SbtNode<'a>>,
+    root: Option<usize>,
+}
+
+/// Build a Sequence Bloom Tree over `dataset`'s columns, keying on `k`-mers.
+/// Leaf and union filters are allocated from `arena`, so they share the
+/// dataset's lifetime and need no separate ownership.
+pub fn build_sbt<'a>(arena: &'a BumpArena, dataset: &DataSet, k: usize) -> SequenceBloomTree<'a> {
+    let mut nodes: Vec<SbtNode<'a>> = Vec::new();
+
+    // One leaf per column, its filter populated from every row's bytes.
+    let mut level: Vec<usize> = Vec::with_capacity(dataset.tables.len());
+    for (column, table) in dataset.tables.iter().enumerate() {
+        let mut bits = vec![0u64; BLOOM_WORDS];
+        if k > 0 {
+            for row in table.rows.iter() {
+                let data = row.data.as_bytes();
+                if data.len() >= k {
+                    for i in 0..=data.len() - k {
+                        for pos in bit_positions(&data[i..i + k]) {
+                            set_bit(&mut bits, pos);
+                        }
+                    }
+                }
+            }
+        }
+        let filter: &'a [u64] = arena.alloc_slice(&bits);
+        nodes.push(SbtNode {
+            filter,
+            column: Some(column),
+            children: [None, None],
+        });
+        level.push(nodes.len() - 1);
+    }
+
+    if level.is_empty() {
+        return SequenceBloomTree {
+            k,
+            nodes,
+            root: None,
+        };
+    }
+
+    // Pair adjacent nodes bottom-up; an odd node at a level carries up unchanged.
+    while level.len() > 1 {
+        let mut next: Vec<usize> = Vec::with_capacity(level.len().div_ceil(2));
+        let mut i = 0;
+        while i < level.len() {
+            if i + 1 < level.len() {
+                let (left, right) = (level[i], level[i + 1]);
+                let mut bits = nodes[left].filter.to_vec();
+                for (word, &other) in bits.iter_mut().zip(nodes[right].filter.iter()) {
+                    *word |= other;
+                }
+                let filter: &'a [u64] = arena.alloc_slice(&bits);
+                nodes.push(SbtNode {
+                    filter,
+                    column: None,
+                    children: [Some(left), Some(right)],
+                });
+                next.push(nodes.len() - 1);
+                i += 2;
+            } else {
+                next.push(level[i]);
+                i += 1;
+            }
+        }
+        level = next;
+    }
+
+    SequenceBloomTree {
+        k,
+        nodes,
+        root: Some(level[0]),
+    }
+}
+
+impl<'a> SequenceBloomTree<'a> {
+    /// Candidate columns for a `%pattern%` query: every leaf whose filter still
+    /// holds all of the pattern's k-mers after pruning. Patterns shorter than
+    /// `k` cannot be decomposed, so every column is returned.
+    pub fn query(&self, pattern: &[u8]) -> Vec<ColumnId> {
+        let kmers = self.pattern_kmers(pattern);
+        // Require every pattern k-mer: a column truly containing the pattern has
+        // them all, and Bloom filters never false-negative, so this never drops
+        // a real match while pruning aggressively.
+        let min_hits = kmers.len();
+        self.query_with_min_hits(pattern, min_hits.max(1))
+    }
+
+    /// Like [`query`](Self::query) but keeps any subtree whose filter holds at
+    /// least `min_hits` of the pattern's k-mers.
+    pub fn query_with_min_hits(&self, pattern: &[u8], min_hits: usize) -> Vec<ColumnId> {
+        let mut candidates = Vec::new();
+        let root = match self.root {
+            Some(root) => root,
+            None => return candidates,
+        };
+
+        let kmers = self.pattern_kmers(pattern);
+        if kmers.is_empty() {
+            // Nothing to prune on; every column is a candidate.
+            self.collect_leaves(root, &mut candidates);
+            return candidates;
+        }
+
+        let mut stack = vec![root];
+        while let Some(node_idx) = stack.pop() {
+            let node = &self.nodes[node_idx];
+            if self.present_kmer_count(node.filter, &kmers) < min_hits {
+                continue; // prune this subtree
+            }
+            match node.column {
+                Some(column) => candidates.push(column),
+                None => {
+                    for child in node.children.into_iter().flatten() {
+                        stack.push(child);
+                    }
+                }
+            }
+        }
+
+        candidates.sort_unstable();
+        candidates
+    }
+
+    fn pattern_kmers(&self, pattern: &[u8]) -> Vec<[usize; BLOOM_HASHES]> {
+        let mut kmers = Vec::new();
+        if self.k > 0 && pattern.len() >= self.k {
+            for i in 0..=pattern.len() - self.k {
+                kmers.push(bit_positions(&pattern[i..i + self.k]));
+            }
+        }
+        kmers
+    }
+
+    fn present_kmer_count(&self, filter: &[u64], kmers: &[[usize; BLOOM_HASHES]]) -> usize {
+        kmers
+            .iter()
+            .filter(|positions| positions.iter().all(|&pos| test_bit(filter, pos)))
+            .count()
+    }
+
+    fn collect_leaves(&self, node_idx: usize, out: &mut Vec<ColumnId>) {
+        let node = &self.nodes[node_idx];
+        match node.column {
+            Some(column) => out.push(column),
+            None => {
+                for child in node.children.into_iter().flatten() {
+                    self.collect_leaves(child, out);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataset::{DataSet, Row, Table};
+    use crate::interner::Interner;
+    use alloc::boxed::Box;
+    use alloc::string::ToString;
+
+    fn single_row_table<'a>(arena: &'a BumpArena, name: &str, data: &str) -> Table<'a> {
+        let interner = Interner::new(arena);
+        let row = Row {
+            id: interner.intern(name),
+            desc: interner.intern(""),
+            data: arena.alloc_str(data),
+        };
+        Table {
+            name: name.to_string(),
+            rows: Box::new([row]),
+            interner,
+        }
+    }
+
+    #[test]
+    fn prunes_non_containing_columns() {
+        let arena = BumpArena::new(1 << 20);
+        let dataset = DataSet {
+            tables: Box::new([
+                single_row_table(&arena, "steel", "BRUSHED STEEL BOX"),
+                single_row_table(&arena, "almond", "roasted almond paste"),
+                single_row_table(&arena, "empty", "nothing relevant here"),
+            ]),
+        };
+
+        let sbt = build_sbt(&arena, &dataset, 3);
+        let hits = sbt.query(b"STEEL");
+        assert_eq!(hits, vec![0]);
+
+        let hits = sbt.query(b"almond");
+        assert_eq!(hits, vec![1]);
+    }
+
+    #[test]
+    fn never_drops_true_column() {
+        let arena = BumpArena::new(1 << 20);
+        let dataset = DataSet {
+            tables: Box::new([
+                single_row_table(&arena, "a", "the quick brown fox"),
+                single_row_table(&arena, "b", "jumps over the lazy dog"),
+            ]),
+        };
+        let sbt = build_sbt(&arena, &dataset, 3);
+        // "lazy" is only in column 1, which must survive.
+        assert!(sbt.query(b"lazy").contains(&1));
+    }
+
+    #[test]
+    fn short_pattern_returns_all_columns() {
+        let arena = BumpArena::new(1 << 20);
+        let dataset = DataSet {
+            tables: Box::new([
+                single_row_table(&arena, "a", "alpha"),
+                single_row_table(&arena, "b", "beta"),
+            ]),
+        };
+        let sbt = build_sbt(&arena, &dataset, 4);
+        // Pattern shorter than k cannot be decomposed into k-mers.
+        assert_eq!(sbt.query(b"xy"), vec![0, 1]);
+    }
+}
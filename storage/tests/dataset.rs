@@ -8,7 +8,7 @@ use storage::{
     BumpArena,
     dataset::{
         Source, SourceKind, infer_source_kind, load_dataset, load_dataset_from_paths,
-        load_fasta_table, load_text_table,
+        load_fasta_table, load_text_table, load_text_table_streaming,
     },
 };
 
@@ -43,6 +43,30 @@ fn load_text_table_basic() {
     assert_eq!(table.rows[0].data, "hello world");
 }
 
+#[test]
+fn load_text_table_streaming_basic() {
+    let arena = BumpArena::new(4096);
+    let contents = "streamed straight into the arena";
+    let reader = std::io::Cursor::new(contents.as_bytes());
+
+    let table = load_text_table_streaming(&arena, reader, contents.len(), "stream.txt")
+        .expect("load streamed text table");
+
+    assert_eq!(table.name, "stream.txt");
+    assert_eq!(table.rows.len(), 1);
+    assert_eq!(table.rows[0].data, contents);
+}
+
+#[test]
+fn load_text_table_streaming_reports_short_read() {
+    let arena = BumpArena::new(4096);
+    let reader = std::io::Cursor::new(b"short".as_slice());
+
+    let err = load_text_table_streaming(&arena, reader, 100, "short.txt")
+        .expect_err("reader has fewer bytes than the declared length");
+    assert!(err.contains("short.txt"));
+}
+
 #[test]
 fn load_fasta_table_basic() {
     let arena = BumpArena::new(4096);
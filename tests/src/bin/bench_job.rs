@@ -7,7 +7,7 @@ use storage::{
 };
 
 mod bench_shared;
-use bench_shared::{run_like_benchmarks, BenchOptions};
+use bench_shared::{run_like_benchmarks, BenchOptions, OutputFormat, DEFAULT_REGRESSION_THRESHOLD};
 
 const DEFAULT_ARENA_GB: usize = 4;
 
@@ -52,6 +52,12 @@ fn main() {
         skip_fftstr1: has_flag("--skip-fftstr1"),
         skip_fm: has_flag("--skip-fm"),
         skip_trigram: has_flag("--skip-trigram"),
+        format: OutputFormat::from_str(&arg_value("--format").unwrap_or_default()),
+        baseline: arg_value("--baseline"),
+        save_baseline: arg_value("--save-baseline"),
+        regression_threshold: arg_value("--regression-threshold")
+            .and_then(|v| v.parse::<f64>().ok())
+            .unwrap_or(DEFAULT_REGRESSION_THRESHOLD),
     };
 
     run_like_benchmarks(&dataset, PATTERNS, options);
@@ -5,7 +5,7 @@ use std::{
 };
 
 use algos::{
-    FftConfig, FftStr0, FftStr1, NaiveScalar, NaiveVectorized, StdSearch, StringSearch, BM, KMP,
+    FftStr0, FftStr1, NaiveScalar, NaiveVectorized, StdSearch, StringSearch, BM, KMP,
 };
 use engine::execute;
 use like::{compile_pattern, compile_pattern_with_options, CompileOptions};
@@ -100,22 +100,17 @@ fn main() {
             );
 
             let entries = match *algo_name {
-                "naive-scalar" => run_benchmark::<NaiveScalar, _>(
-                    algo_name,
-                    pat_str,
-                    pattern_index,
-                    &database,
-                    |_, pat| unsafe { std::mem::transmute::<&[u8], &[u8]>(pat.as_bytes()) },
-                ),
+                "naive-scalar" => {
+                    run_benchmark::<NaiveScalar>(algo_name, pat_str, pattern_index, &database)
+                }
                 "naive-vector" => {
                     #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
                     {
-                        run_benchmark::<NaiveVectorized, _>(
+                        run_benchmark::<NaiveVectorized>(
                             algo_name,
                             pat_str,
                             pattern_index,
                             &database,
-                            |_, pat| unsafe { std::mem::transmute::<&[u8], &[u8]>(pat.as_bytes()) },
                         )
                     }
 
@@ -125,37 +120,18 @@ fn main() {
                         Vec::new()
                     }
                 }
-                "kmp" => run_benchmark::<KMP, _>(
-                    algo_name,
-                    pat_str,
-                    pattern_index,
-                    &database,
-                    |_, pat| unsafe { std::mem::transmute::<&[u8], &[u8]>(pat.as_bytes()) },
-                ),
-                "bm" => run_benchmark::<BM, _>(
-                    algo_name,
-                    pat_str,
-                    pattern_index,
-                    &database,
-                    |_, pat| unsafe { std::mem::transmute::<&[u8], &[u8]>(pat.as_bytes()) },
-                ),
-                "std" => run_benchmark::<StdSearch, _>(
-                    algo_name,
-                    pat_str,
-                    pattern_index,
-                    &database,
-                    |_, pat| unsafe { std::mem::transmute::<&str, &str>(pat) },
-                ),
+                "kmp" => run_benchmark::<KMP>(algo_name, pat_str, pattern_index, &database),
+                "bm" => run_benchmark::<BM>(algo_name, pat_str, pattern_index, &database),
+                "std" => run_benchmark::<StdSearch>(algo_name, pat_str, pattern_index, &database),
                 "fftstr0" => {
                     if should_skip_fftstr0(pat_str) {
                         skipped_entries(algo_name, pat_str, pattern_index, &database)
                     } else {
-                        run_benchmark_with_options::<FftStr0, _>(
+                        run_benchmark_with_options::<FftStr0>(
                             algo_name,
                             pat_str,
                             pattern_index,
                             &database,
-                            |_, pat| FftConfig::from_str(pat),
                             CompileOptions {
                                 treat_underscore_as_literal: true,
                                 literal_underscore_is_wildcard: true,
@@ -167,12 +143,11 @@ fn main() {
                     if should_skip_fftstr1(pat_str) {
                         skipped_entries(algo_name, pat_str, pattern_index, &database)
                     } else {
-                        run_benchmark_with_options::<FftStr1, _>(
+                        run_benchmark_with_options::<FftStr1>(
                             algo_name,
                             pat_str,
                             pattern_index,
                             &database,
-                            |_, pat| FftConfig::from_str(pat),
                             CompileOptions {
                                 treat_underscore_as_literal: true,
                                 literal_underscore_is_wildcard: true,
@@ -209,20 +184,18 @@ fn load_database<'a>(arena: &'a BumpArena) -> DataSet<'a> {
     load_dataset_from_paths(arena, &paths).expect("load dataset")
 }
 
-fn run_benchmark<'a, S, F>(
+fn run_benchmark<'a, S>(
     algo_name: &str,
     pat_str: &str,
     pattern_index: usize,
     database: &'a DataSet<'a>,
-    factory: F,
 ) -> Vec<ResultEntry>
 where
     S: StringSearch,
-    F: FnMut(&mut (), &str) -> S::Config + Clone,
 {
     let mut results = Vec::new();
 
-    let pattern = compile_pattern::<S, _, _>(pat_str, (), factory);
+    let pattern = compile_pattern::<S>(pat_str);
 
     for table in database.tables.iter() {
         let table_dataset = DataSet {
@@ -248,21 +221,19 @@ where
     results
 }
 
-fn run_benchmark_with_options<'a, S, F>(
+fn run_benchmark_with_options<'a, S>(
     algo_name: &str,
     pat_str: &str,
     pattern_index: usize,
     database: &'a DataSet<'a>,
-    factory: F,
     options: CompileOptions,
 ) -> Vec<ResultEntry>
 where
     S: StringSearch,
-    F: FnMut(&mut (), &str) -> S::Config + Clone,
 {
     let mut results = Vec::new();
 
-    let pattern = compile_pattern_with_options::<S, _, _>(pat_str, (), factory, options);
+    let pattern = compile_pattern_with_options::<S>(pat_str, options);
 
     for table in database.tables.iter() {
         let table_dataset = DataSet {
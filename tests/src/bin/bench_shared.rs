@@ -1,15 +1,16 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::HashMap,
     path::Path,
     time::{Duration, Instant},
 };
 
 use algos::{
-    FMIndex, FftConfig, FftStr0, FftStr1, NaiveScalar, NaiveVectorized, StdSearch, StringSearch,
+    FMIndex, FftStr0, FftStr1, NaiveScalar, NaiveVectorized, StdSearch, StringSearch,
     TrigramIndex, BM, KMP,
 };
 use engine::execute;
 use like::{compile_pattern, compile_pattern_with_options, like_match, CompileOptions, Pattern};
+use roaring::RoaringBitmap;
 use storage::dataset::DataSet;
 
 const FM_SEPARATOR: u8 = 0x1F;
@@ -35,9 +36,209 @@ struct ResultEntry {
     pattern: String,
     file: String,
     file_type: String,
-    duration: Duration,
+    timing: TimingStats,
     found_count: usize,
     skipped: bool,
+    /// Cost-based literal plan the `fm` algorithm chose for this query, when
+    /// applicable: the literals in the order the intersection processed them
+    /// (most selective first) and the suffix-array range length that seeded
+    /// it. `None` for every other algorithm and for FM queries that took a
+    /// simple-pattern, full-scan or fixed-gap path, or that hit a literal
+    /// absent from the index.
+    fm_plan: Option<FmPlan>,
+}
+
+/// Target total measured wall-time per cell before adaptive sampling stops.
+const MEASURE_TARGET_MS: u128 = 100;
+
+/// Number of bootstrap resamples used to estimate the median's confidence
+/// interval. High enough for stable 2.5th/97.5th percentiles, low enough to
+/// stay negligible next to the measurement loop itself.
+const BOOTSTRAP_RESAMPLES: usize = 1000;
+
+/// Distribution of per-iteration timings for one benchmark cell. A single
+/// `Instant::now()` pair is dominated by cache warmup and scheduler noise; this
+/// records many iterations and summarizes them robustly so the speed ranking is
+/// reproducible.
+#[derive(Debug, Clone)]
+struct TimingStats {
+    /// Raw per-iteration samples in nanoseconds. Kept verbatim for serialization
+    /// and baseline comparison; the summary figures below are computed on the
+    /// outlier-filtered subset, except `mad_ns`, which spans every sample.
+    samples: Vec<u128>,
+    count: usize,
+    min_ns: f64,
+    median_ns: f64,
+    mean_ns: f64,
+    p95_ns: f64,
+    mad_ns: f64,
+    /// 95% bootstrap confidence interval for the median, from resampling the
+    /// outlier-filtered samples [`BOOTSTRAP_RESAMPLES`] times.
+    median_ci_low_ns: f64,
+    median_ci_high_ns: f64,
+    /// Set when the relative standard error of the mean exceeds 5%, i.e. the
+    /// cell is too noisy to trust small differences against.
+    noisy: bool,
+}
+
+impl TimingStats {
+    fn zero() -> Self {
+        Self {
+            samples: Vec::new(),
+            count: 0,
+            min_ns: 0.0,
+            median_ns: 0.0,
+            mean_ns: 0.0,
+            p95_ns: 0.0,
+            mad_ns: 0.0,
+            median_ci_low_ns: 0.0,
+            median_ci_high_ns: 0.0,
+            noisy: false,
+        }
+    }
+
+    fn from_samples(samples: Vec<u128>) -> Self {
+        if samples.is_empty() {
+            return Self::zero();
+        }
+
+        let mut sorted: Vec<f64> = samples.iter().map(|&s| s as f64).collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let median = percentile(&sorted, 0.5);
+
+        // MAD = median(|xᵢ − median|), a noise-robust spread estimate, kept for
+        // display even though outlier rejection below uses Tukey's fence.
+        let mut dev: Vec<f64> = sorted.iter().map(|v| (v - median).abs()).collect();
+        dev.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mad = percentile(&dev, 0.5);
+
+        // Tukey's fence: discard samples outside [Q1 − 1.5·IQR, Q3 + 1.5·IQR].
+        // Distribution-free and a standard choice for skewed, heavy-tailed
+        // timing data where a handful of scheduler-preempted iterations would
+        // otherwise drag the mean around.
+        let q1 = percentile(&sorted, 0.25);
+        let q3 = percentile(&sorted, 0.75);
+        let iqr = q3 - q1;
+        let lower_fence = q1 - 1.5 * iqr;
+        let upper_fence = q3 + 1.5 * iqr;
+        let mut kept: Vec<f64> = sorted
+            .iter()
+            .copied()
+            .filter(|v| *v >= lower_fence && *v <= upper_fence)
+            .collect();
+        if kept.is_empty() {
+            kept = sorted.clone();
+        }
+
+        let n = kept.len();
+        let min = kept[0];
+        let median_kept = percentile(&kept, 0.5);
+        let mean = kept.iter().sum::<f64>() / n as f64;
+        let p95 = percentile(&kept, 0.95);
+        let variance = kept.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n as f64;
+        let stddev = variance.sqrt();
+        let rse = if mean > 0.0 {
+            stddev / mean / (n as f64).sqrt()
+        } else {
+            0.0
+        };
+        let (ci_low, ci_high) = bootstrap_median_ci(&kept, BOOTSTRAP_RESAMPLES);
+
+        Self {
+            samples,
+            count: n,
+            min_ns: min,
+            median_ns: median_kept,
+            mean_ns: mean,
+            p95_ns: p95,
+            mad_ns: mad,
+            median_ci_low_ns: ci_low,
+            median_ci_high_ns: ci_high,
+            noisy: rse > 0.05,
+        }
+    }
+
+    fn median_us(&self) -> f64 {
+        self.median_ns / 1000.0
+    }
+}
+
+/// Linear-interpolated quantile of an already-sorted slice.
+fn percentile(sorted: &[f64], q: f64) -> f64 {
+    match sorted.len() {
+        0 => 0.0,
+        1 => sorted[0],
+        len => {
+            let pos = q * (len - 1) as f64;
+            let lo = pos.floor() as usize;
+            let hi = pos.ceil() as usize;
+            if lo == hi {
+                sorted[lo]
+            } else {
+                let frac = pos - lo as f64;
+                sorted[lo] * (1.0 - frac) + sorted[hi] * frac
+            }
+        }
+    }
+}
+
+/// Percentile bootstrap confidence interval for the median: resample `kept`
+/// with replacement `resamples` times, take each resample's median, and
+/// report the 2.5th/97.5th percentiles of that distribution as the 95% CI.
+/// Seeded from the samples themselves (not wall-clock entropy) so a rerun of
+/// the same measurement reports the same interval.
+fn bootstrap_median_ci(kept: &[f64], resamples: usize) -> (f64, f64) {
+    if kept.len() <= 1 {
+        let v = kept.first().copied().unwrap_or(0.0);
+        return (v, v);
+    }
+
+    let mut rng = kept.iter().fold(0x9E3779B97F4A7C15u64, |acc, v| {
+        acc.wrapping_add(v.to_bits())
+            .wrapping_mul(0xBF58_476D_1CE4_E5B9)
+    });
+
+    let mut resample = vec![0.0; kept.len()];
+    let mut medians = Vec::with_capacity(resamples);
+    for _ in 0..resamples {
+        for slot in resample.iter_mut() {
+            rng ^= rng << 13;
+            rng ^= rng >> 7;
+            rng ^= rng << 17;
+            *slot = kept[(rng as usize) % kept.len()];
+        }
+        resample.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        medians.push(percentile(&resample, 0.5));
+    }
+    medians.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    (percentile(&medians, 0.025), percentile(&medians, 0.975))
+}
+
+/// Run `search` with an untimed warmup, then repeatedly — doubling the
+/// iteration count each round — until the total measured wall-time exceeds
+/// [`MEASURE_TARGET_MS`], recording every per-iteration time. Returns the timing
+/// distribution and the found-count from the warmup pass.
+fn measure<F: FnMut() -> usize>(mut search: F) -> (TimingStats, usize) {
+    let found = search();
+
+    let mut samples: Vec<u128> = Vec::new();
+    let mut iters = 1usize;
+    let overall = Instant::now();
+    loop {
+        for _ in 0..iters {
+            let start = Instant::now();
+            let _ = search();
+            samples.push(start.elapsed().as_nanos());
+        }
+        if overall.elapsed().as_millis() >= MEASURE_TARGET_MS {
+            break;
+        }
+        iters = iters.saturating_mul(2);
+    }
+
+    (TimingStats::from_samples(samples), found)
 }
 
 #[derive(Debug)]
@@ -62,7 +263,6 @@ struct FmIndexDatabase<'a> {
     fm: FMIndex,
     rows: Vec<FmRow<'a>>,
     row_starts: Vec<usize>,
-    byte_freq: [usize; 256],
     max_range: usize,
 }
 
@@ -77,7 +277,7 @@ struct TrigramRow<'a> {
     data: &'a str,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct BenchOptions {
     pub skip_naive_scalar: bool,
     pub skip_naive_vector: bool,
@@ -89,6 +289,61 @@ pub struct BenchOptions {
     pub skip_fftstr1: bool,
     pub skip_fm: bool,
     pub skip_trigram: bool,
+    /// How the collected results are emitted once all cells have run.
+    pub format: OutputFormat,
+    /// When set, load this JSON baseline and print a regression report against
+    /// the current run's median times.
+    pub baseline: Option<String>,
+    /// When set, write the current run's results to this path as JSON so a later
+    /// run can gate against it.
+    pub save_baseline: Option<String>,
+    /// Fractional slowdown above which a cell counts as a regression and the
+    /// process exits non-zero (e.g. `0.05` for 5%).
+    pub regression_threshold: f64,
+}
+
+/// Default gate: a cell more than 5% slower than its baseline fails the run.
+pub const DEFAULT_REGRESSION_THRESHOLD: f64 = 0.05;
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            skip_naive_scalar: false,
+            skip_naive_vector: false,
+            skip_kmp: false,
+            skip_bm: false,
+            skip_std: false,
+            skip_lut_short: false,
+            skip_fftstr0: false,
+            skip_fftstr1: false,
+            skip_fm: false,
+            skip_trigram: false,
+            format: OutputFormat::Text,
+            baseline: None,
+            save_baseline: None,
+            regression_threshold: DEFAULT_REGRESSION_THRESHOLD,
+        }
+    }
+}
+
+/// How benchmark results are rendered. `Text` keeps the human-readable tables;
+/// `Json` and `Csv` emit the full result set for tracking across commits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+impl OutputFormat {
+    /// Parse a `--format` value; unknown strings fall back to `Text`.
+    pub fn from_str(value: &str) -> Self {
+        match value {
+            "json" => OutputFormat::Json,
+            "csv" => OutputFormat::Csv,
+            _ => OutputFormat::Text,
+        }
+    }
 }
 
 impl<'a> FmIndexDatabase<'a> {
@@ -139,7 +394,7 @@ pub fn run_like_benchmarks(
         Some(trigram)
     };
 
-    let mut fm_literal_cache: HashMap<String, HashSet<usize>> = HashMap::new();
+    let mut fm_literal_cache: HashMap<String, RoaringBitmap> = HashMap::new();
 
     let mut results = Vec::new();
 
@@ -155,12 +410,11 @@ pub fn run_like_benchmarks(
                     if options.skip_naive_scalar {
                         skipped_entries(algo_name, pat_str, pattern_index, database)
                     } else {
-                        run_benchmark::<NaiveScalar, _>(
+                        run_benchmark::<NaiveScalar>(
                             algo_name,
                             pat_str,
                             pattern_index,
                             database,
-                            |_, pat| unsafe { std::mem::transmute::<&[u8], &[u8]>(pat.as_bytes()) },
                         )
                     }
                 }
@@ -168,12 +422,11 @@ pub fn run_like_benchmarks(
                     if skip_naive_vector {
                         skipped_entries(algo_name, pat_str, pattern_index, database)
                     } else {
-                        run_benchmark::<NaiveVectorized, _>(
+                        run_benchmark::<NaiveVectorized>(
                             algo_name,
                             pat_str,
                             pattern_index,
                             database,
-                            |_, pat| unsafe { std::mem::transmute::<&[u8], &[u8]>(pat.as_bytes()) },
                         )
                     }
                 }
@@ -181,51 +434,32 @@ pub fn run_like_benchmarks(
                     if options.skip_kmp {
                         skipped_entries(algo_name, pat_str, pattern_index, database)
                     } else {
-                        run_benchmark::<KMP, _>(
-                            algo_name,
-                            pat_str,
-                            pattern_index,
-                            database,
-                            |_, pat| unsafe { std::mem::transmute::<&[u8], &[u8]>(pat.as_bytes()) },
-                        )
+                        run_benchmark::<KMP>(algo_name, pat_str, pattern_index, database)
                     }
                 }
                 "bm" => {
                     if options.skip_bm {
                         skipped_entries(algo_name, pat_str, pattern_index, database)
                     } else {
-                        run_benchmark::<BM, _>(
-                            algo_name,
-                            pat_str,
-                            pattern_index,
-                            database,
-                            |_, pat| unsafe { std::mem::transmute::<&[u8], &[u8]>(pat.as_bytes()) },
-                        )
+                        run_benchmark::<BM>(algo_name, pat_str, pattern_index, database)
                     }
                 }
                 "std" => {
                     if options.skip_std {
                         skipped_entries(algo_name, pat_str, pattern_index, database)
                     } else {
-                        run_benchmark::<StdSearch, _>(
-                            algo_name,
-                            pat_str,
-                            pattern_index,
-                            database,
-                            |_, pat| unsafe { std::mem::transmute::<&str, &str>(pat) },
-                        )
+                        run_benchmark::<StdSearch>(algo_name, pat_str, pattern_index, database)
                     }
                 }
                 "lut-short" => {
                     if options.skip_lut_short || skip_lut_short {
                         skipped_entries(algo_name, pat_str, pattern_index, database)
                     } else {
-                        run_benchmark::<algos::LutShort, _>(
+                        run_benchmark::<algos::LutShort>(
                             algo_name,
                             pat_str,
                             pattern_index,
                             database,
-                            |_, pat| unsafe { std::mem::transmute::<&[u8], &[u8]>(pat.as_bytes()) },
                         )
                     }
                 }
@@ -233,12 +467,11 @@ pub fn run_like_benchmarks(
                     if options.skip_fftstr0 || should_skip_fftstr0(pat_str) {
                         skipped_entries(algo_name, pat_str, pattern_index, database)
                     } else {
-                        run_benchmark_with_options::<FftStr0, _>(
+                        run_benchmark_with_options::<FftStr0>(
                             algo_name,
                             pat_str,
                             pattern_index,
                             database,
-                            |_, pat| FftConfig::from_str(pat),
                             CompileOptions {
                                 treat_underscore_as_literal: true,
                                 literal_underscore_is_wildcard: true,
@@ -250,12 +483,11 @@ pub fn run_like_benchmarks(
                     if options.skip_fftstr1 || should_skip_fftstr1(pat_str) {
                         skipped_entries(algo_name, pat_str, pattern_index, database)
                     } else {
-                        run_benchmark_with_options::<FftStr1, _>(
+                        run_benchmark_with_options::<FftStr1>(
                             algo_name,
                             pat_str,
                             pattern_index,
                             database,
-                            |_, pat| FftConfig::from_str(pat),
                             CompileOptions {
                                 treat_underscore_as_literal: true,
                                 literal_underscore_is_wildcard: true,
@@ -300,11 +532,271 @@ pub fn run_like_benchmarks(
         }
     }
 
-    print_summary_table(&results);
-    print_algo_ranking(&results);
-    print_per_pattern_ranking(&results);
-    print_per_file_ranking(&results);
-    print_correctness_report(&results);
+    match options.format {
+        OutputFormat::Text => {
+            print_summary_table(&results);
+            print_algo_ranking(&results);
+            print_per_pattern_ranking(&results);
+            print_per_file_ranking(&results);
+            print_correctness_report(&results);
+        }
+        OutputFormat::Json => println!("{}", results_to_json(&results)),
+        OutputFormat::Csv => print!("{}", results_to_csv(&results)),
+    }
+
+    if let Some(path) = &options.save_baseline {
+        match std::fs::write(path, results_to_json(&results)) {
+            Ok(()) => eprintln!("> Saved baseline to {}", path),
+            Err(e) => eprintln!("! Failed to write baseline {}: {}", path, e),
+        }
+    }
+
+    if let Some(path) = &options.baseline {
+        match load_baseline(path) {
+            Ok(baseline) => {
+                let regressed =
+                    print_regression_report(&results, &baseline, options.regression_threshold);
+                if regressed {
+                    std::process::exit(1);
+                }
+            }
+            Err(e) => eprintln!("! Failed to read baseline {}: {}", path, e),
+        }
+    }
+}
+
+/// Serialize the full result set to a JSON array. No serde in this tree, so the
+/// record is built by hand the same way the CLI emits its match records.
+fn results_to_json(results: &[ResultEntry]) -> String {
+    let mut out = String::from("[");
+    for (i, entry) in results.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push('{');
+        out.push_str(&format!("\"algo\":{},", json_string(&entry.algo)));
+        out.push_str(&format!("\"pattern_index\":{},", entry.pattern_index));
+        out.push_str(&format!("\"pattern\":{},", json_string(&entry.pattern)));
+        out.push_str(&format!("\"file\":{},", json_string(&entry.file)));
+        out.push_str(&format!("\"file_type\":{},", json_string(&entry.file_type)));
+        out.push_str("\"timing\":");
+        out.push_str(&timing_to_json(&entry.timing));
+        out.push_str(&format!(",\"found_count\":{},", entry.found_count));
+        out.push_str(&format!("\"skipped\":{},", entry.skipped));
+        out.push_str(&format!(
+            "\"fm_plan\":{}",
+            fm_plan_to_json(entry.fm_plan.as_ref())
+        ));
+        out.push('}');
+    }
+    out.push(']');
+    out
+}
+
+fn timing_to_json(timing: &TimingStats) -> String {
+    let samples: Vec<String> = timing.samples.iter().map(|s| s.to_string()).collect();
+    format!(
+        "{{\"count\":{},\"min_ns\":{},\"median_ns\":{},\"mean_ns\":{},\"p95_ns\":{},\"mad_ns\":{},\"median_ci_low_ns\":{},\"median_ci_high_ns\":{},\"noisy\":{},\"samples_ns\":[{}]}}",
+        timing.count,
+        timing.min_ns,
+        timing.median_ns,
+        timing.mean_ns,
+        timing.p95_ns,
+        timing.mad_ns,
+        timing.median_ci_low_ns,
+        timing.median_ci_high_ns,
+        timing.noisy,
+        samples.join(","),
+    )
+}
+
+fn fm_plan_to_json(plan: Option<&FmPlan>) -> String {
+    match plan {
+        None => "null".to_string(),
+        Some(plan) => {
+            let literals: Vec<String> =
+                plan.literal_order.iter().map(|l| json_string(l)).collect();
+            format!(
+                "{{\"literal_order\":[{}],\"selectivity\":{}}}",
+                literals.join(","),
+                plan.selectivity,
+            )
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn results_to_csv(results: &[ResultEntry]) -> String {
+    let mut out = String::from(
+        "algo,pattern_index,file,file_type,found_count,skipped,median_ns,mean_ns,p95_ns,min_ns,median_ci_low_ns,median_ci_high_ns,noisy,fm_plan_literals,fm_plan_selectivity\n",
+    );
+    for entry in results {
+        let (fm_plan_literals, fm_plan_selectivity) = match &entry.fm_plan {
+            Some(plan) => (plan.literal_order.join("|"), plan.selectivity.to_string()),
+            None => (String::new(), String::new()),
+        };
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+            csv_field(&entry.algo),
+            entry.pattern_index,
+            csv_field(&entry.file),
+            csv_field(&entry.file_type),
+            entry.found_count,
+            entry.skipped,
+            entry.timing.median_ns,
+            entry.timing.mean_ns,
+            entry.timing.p95_ns,
+            entry.timing.min_ns,
+            entry.timing.median_ci_low_ns,
+            entry.timing.median_ci_high_ns,
+            entry.timing.noisy,
+            csv_field(&fm_plan_literals),
+            fm_plan_selectivity,
+        ));
+    }
+    out
+}
+
+/// Quote a CSV field only when it contains a separator, quote, or newline.
+fn csv_field(s: &str) -> String {
+    if s.contains(',') || s.contains('"') || s.contains('\n') {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// A baseline cell keyed by (algo, pattern, file) — the pattern's text rather
+/// than its index, so baselines stay comparable even if a later run
+/// reorders or extends the pattern list — with its recorded median time and
+/// the high end of its 95% CI, both in nanoseconds.
+struct BaselineCell {
+    key: (String, String, String),
+    median_ns: f64,
+    median_ci_high_ns: f64,
+}
+
+/// Parse a baseline JSON file previously written by `--save-baseline`. Only the
+/// fields needed for matching and comparison are extracted. Baseline files from
+/// multiple runs can simply be concatenated as JSON arrays and re-saved to
+/// build up a longitudinal dataset; this function keeps every entry, and
+/// `print_regression_report` compares against the last one for each key (the
+/// most recent, when later runs are appended), so repeated cells in a merged
+/// file don't need deduplicating up front.
+fn load_baseline(path: &str) -> std::io::Result<Vec<BaselineCell>> {
+    let text = std::fs::read_to_string(path)?;
+    let value = JsonValue::parse(&text)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+    let mut cells = Vec::new();
+    if let JsonValue::Array(entries) = value {
+        for entry in entries {
+            let algo = entry.get("algo").and_then(JsonValue::as_str);
+            let pattern = entry.get("pattern").and_then(JsonValue::as_str);
+            let file = entry.get("file").and_then(JsonValue::as_str);
+            let timing = entry.get("timing");
+            let median = timing
+                .and_then(|t| t.get("median_ns"))
+                .and_then(JsonValue::as_f64);
+            // Older baselines predate the CI fields; fall back to the median
+            // itself so a regression check against them degrades to the
+            // fixed-percentage threshold instead of always flagging.
+            let median_ci_high = timing
+                .and_then(|t| t.get("median_ci_high_ns"))
+                .and_then(JsonValue::as_f64)
+                .or(median);
+            if let (Some(algo), Some(pattern), Some(file), Some(median), Some(median_ci_high)) =
+                (algo, pattern, file, median, median_ci_high)
+            {
+                cells.push(BaselineCell {
+                    key: (algo.to_string(), pattern.to_string(), file.to_string()),
+                    median_ns: median,
+                    median_ci_high_ns: median_ci_high,
+                });
+            }
+        }
+    }
+    Ok(cells)
+}
+
+/// Compare the current results against a baseline cell-by-cell, printing the
+/// median-time ratio for each match. A cell is flagged `SLOWER` if it crosses
+/// the fixed `threshold` fraction *or* its new median falls outside the
+/// baseline's own 95% CI — the latter catches a real slowdown too small to
+/// clear a fixed percentage but still statistically distinguishable from the
+/// baseline's measurement noise. Returns `true` if any cell regressed.
+fn print_regression_report(
+    results: &[ResultEntry],
+    baseline: &[BaselineCell],
+    threshold: f64,
+) -> bool {
+    println!("\n\n{:=^82}", " REGRESSION REPORT ");
+    println!(
+        "{:<15} | {:<20} | {:<20} | {:>10} | {:<10}",
+        "Algorithm", "Pattern", "File", "Ratio", "Status"
+    );
+    println!("{:-^82}", "");
+
+    let mut regressed = false;
+    for entry in results.iter().filter(|e| !e.skipped) {
+        let key = (entry.algo.clone(), entry.pattern.clone(), entry.file.clone());
+        let base = match baseline.iter().rev().find(|c| c.key == key) {
+            Some(base) if base.median_ns > 0.0 => base,
+            _ => continue,
+        };
+
+        let ratio = entry.timing.median_ns / base.median_ns;
+        let outside_baseline_ci = entry.timing.median_ns > base.median_ci_high_ns;
+        let status = if entry.timing.noisy {
+            "noisy"
+        } else if ratio - 1.0 > threshold {
+            regressed = true;
+            "SLOWER"
+        } else if outside_baseline_ci {
+            regressed = true;
+            "SLOWER(ci)"
+        } else if 1.0 - ratio > threshold {
+            "faster"
+        } else {
+            "ok"
+        };
+
+        let pattern_display = if entry.pattern.len() > 20 {
+            format!("{}...", &entry.pattern[..17])
+        } else {
+            entry.pattern.clone()
+        };
+        let file_display = if entry.file.len() > 20 {
+            format!("{}...", &entry.file[..17])
+        } else {
+            entry.file.clone()
+        };
+
+        println!(
+            "{:<15} | {:<20} | {:<20} | {:>9.2}x | {:<10}",
+            entry.algo, pattern_display, file_display, ratio, status
+        );
+    }
+
+    println!("{:=^82}", if regressed { " REGRESSIONS FOUND " } else { " OK " });
+    regressed
 }
 
 fn lut_short_available() -> bool {
@@ -317,29 +809,25 @@ fn naive_vector_available() -> bool {
         || cfg!(all(target_arch = "aarch64", target_feature = "neon"))
 }
 
-fn run_benchmark<'a, S, F>(
+fn run_benchmark<'a, S>(
     algo_name: &str,
     pat_str: &str,
     pattern_index: usize,
     database: &'a DataSet<'a>,
-    factory: F,
 ) -> Vec<ResultEntry>
 where
     S: StringSearch,
-    F: FnMut(&mut (), &str) -> S::Config + Clone,
 {
     let mut results = Vec::new();
 
-    let pattern = compile_pattern::<S, _, _>(pat_str, (), factory);
+    let pattern = compile_pattern::<S>(pat_str);
 
     for table in database.tables.iter() {
         let table_dataset = DataSet {
             tables: vec![table.clone()].into_boxed_slice(),
         };
 
-        let start = Instant::now();
-        let matches = execute(&pattern, &table_dataset);
-        let duration = start.elapsed();
+        let (timing, found_count) = measure(|| execute(&pattern, &table_dataset).len());
 
         results.push(ResultEntry {
             algo: algo_name.to_string(),
@@ -347,9 +835,10 @@ where
             pattern: pat_str.to_string(),
             file: table.name.clone(),
             file_type: infer_file_type(&table.name),
-            duration,
-            found_count: matches.len(),
+            timing,
+            found_count,
             skipped: false,
+            fm_plan: None,
         });
     }
 
@@ -362,10 +851,10 @@ fn run_fm_benchmark<'a>(
     pattern_index: usize,
     database: &'a DataSet<'a>,
     fm_database: &FmIndexDatabase<'a>,
-    fm_literal_cache: &mut HashMap<String, HashSet<usize>>,
+    fm_literal_cache: &mut HashMap<String, RoaringBitmap>,
 ) -> Vec<ResultEntry> {
     let mut results = Vec::new();
-    let pattern = compile_pattern::<StdSearch, _, _>(pat_str, (), |_, pat| pat);
+    let pattern = compile_pattern::<StdSearch>(pat_str);
 
     for table in database.tables.iter() {
         if table.rows.is_empty() {
@@ -375,17 +864,19 @@ fn run_fm_benchmark<'a>(
                 pattern: pat_str.to_string(),
                 file: table.name.clone(),
                 file_type: infer_file_type(&table.name),
-                duration: Duration::from_micros(0),
+                timing: TimingStats::zero(),
                 found_count: 0,
                 skipped: false,
+                fm_plan: None,
             });
             continue;
         }
         let table_name = table.name.as_str();
-        let start = Instant::now();
-        let found =
+        let (_, plan) =
             fm_like_search_table(fm_database, table_name, &pattern, pat_str, fm_literal_cache);
-        let duration = start.elapsed();
+        let (timing, found) = measure(|| {
+            fm_like_search_table(fm_database, table_name, &pattern, pat_str, fm_literal_cache).0
+        });
 
         results.push(ResultEntry {
             algo: algo_name.to_string(),
@@ -393,9 +884,10 @@ fn run_fm_benchmark<'a>(
             pattern: pat_str.to_string(),
             file: table.name.clone(),
             file_type: infer_file_type(&table.name),
-            duration,
+            timing,
             found_count: found,
             skipped: false,
+            fm_plan: plan,
         });
     }
 
@@ -410,7 +902,7 @@ fn run_trigram_benchmark<'a>(
     trigram_database: &TrigramDatabase<'a>,
 ) -> Vec<ResultEntry> {
     let mut results = Vec::new();
-    let pattern = compile_pattern::<StdSearch, _, _>(pat_str, (), |_, pat| pat);
+    let pattern = compile_pattern::<StdSearch>(pat_str);
 
     for table in database.tables.iter() {
         if table.rows.is_empty() {
@@ -420,16 +912,17 @@ fn run_trigram_benchmark<'a>(
                 pattern: pat_str.to_string(),
                 file: table.name.clone(),
                 file_type: infer_file_type(&table.name),
-                duration: Duration::from_micros(0),
+                timing: TimingStats::zero(),
                 found_count: 0,
                 skipped: false,
+                fm_plan: None,
             });
             continue;
         }
         let table_name = table.name.as_str();
-        let start = Instant::now();
-        let found = trigram_like_search_table(trigram_database, table_name, &pattern, pat_str);
-        let duration = start.elapsed();
+        let (timing, found) = measure(|| {
+            trigram_like_search_table(trigram_database, table_name, &pattern, pat_str)
+        });
 
         results.push(ResultEntry {
             algo: algo_name.to_string(),
@@ -437,39 +930,36 @@ fn run_trigram_benchmark<'a>(
             pattern: pat_str.to_string(),
             file: table.name.clone(),
             file_type: infer_file_type(&table.name),
-            duration,
+            timing,
             found_count: found,
             skipped: false,
+            fm_plan: None,
         });
     }
 
     results
 }
 
-fn run_benchmark_with_options<'a, S, F>(
+fn run_benchmark_with_options<'a, S>(
     algo_name: &str,
     pat_str: &str,
     pattern_index: usize,
     database: &'a DataSet<'a>,
-    factory: F,
     options: CompileOptions,
 ) -> Vec<ResultEntry>
 where
     S: StringSearch,
-    F: FnMut(&mut (), &str) -> S::Config + Clone,
 {
     let mut results = Vec::new();
 
-    let pattern = compile_pattern_with_options::<S, _, _>(pat_str, (), factory, options);
+    let pattern = compile_pattern_with_options::<S>(pat_str, options);
 
     for table in database.tables.iter() {
         let table_dataset = DataSet {
             tables: vec![table.clone()].into_boxed_slice(),
         };
 
-        let start = Instant::now();
-        let matches = execute(&pattern, &table_dataset);
-        let duration = start.elapsed();
+        let (timing, found_count) = measure(|| execute(&pattern, &table_dataset).len());
 
         results.push(ResultEntry {
             algo: algo_name.to_string(),
@@ -477,55 +967,115 @@ where
             pattern: pat_str.to_string(),
             file: table.name.clone(),
             file_type: infer_file_type(&table.name),
-            duration,
-            found_count: matches.len(),
+            timing,
+            found_count,
             skipped: false,
+            fm_plan: None,
         });
     }
 
     results
 }
 
+/// A cost-based literal plan for one `fm_like_search_table` query: the order
+/// literals were intersected in, most-selective first, and the suffix-array
+/// range length (exact occurrence count) of the literal that drove it.
+#[derive(Debug, Clone, Default)]
+struct FmPlan {
+    literal_order: Vec<String>,
+    selectivity: usize,
+}
+
 fn fm_like_search_table<'a>(
     fm_database: &FmIndexDatabase<'a>,
     table_name: &str,
     pattern: &Pattern<'a, StdSearch>,
     pattern_str: &str,
-    fm_literal_cache: &mut HashMap<String, HashSet<usize>>,
-) -> usize {
-    match simple_like_kind(pattern_str) {
-        SimpleLike::All => return count_all_rows(fm_database, table_name),
-        SimpleLike::Exact(lit) => return count_exact_rows(fm_database, table_name, lit),
+    fm_literal_cache: &mut HashMap<String, RoaringBitmap>,
+) -> (usize, Option<FmPlan>) {
+    let plan = build_like_plan(pattern_str);
+    match plan.simple {
+        SimpleLike::All => return (count_all_rows(fm_database, table_name), None),
+        SimpleLike::Exact(lit) => return (count_exact_rows(fm_database, table_name, lit), None),
         SimpleLike::Contains(lit) => {
-            return count_rows_with_literal(fm_database, table_name, lit, fm_literal_cache)
+            return (
+                count_rows_with_literal(fm_database, table_name, lit, fm_literal_cache),
+                None,
+            )
+        }
+        SimpleLike::Prefix(lit) => {
+            return (count_rows_with_prefix(fm_database, table_name, lit), None)
+        }
+        SimpleLike::Suffix(lit) => {
+            return (count_rows_with_suffix(fm_database, table_name, lit), None)
         }
-        SimpleLike::Prefix(lit) => return count_rows_with_prefix(fm_database, table_name, lit),
-        SimpleLike::Suffix(lit) => return count_rows_with_suffix(fm_database, table_name, lit),
         SimpleLike::Complex => {}
     }
 
-    let mut literals = split_literals(pattern_str);
-    if literals.is_empty() {
-        return count_like_match_all(fm_database, table_name, pattern);
+    if plan.full_scan {
+        return (count_like_match_all(fm_database, table_name, pattern), None);
     }
 
-    literals.sort_by_key(|lit| literal_rarity(lit, &fm_database.byte_freq));
+    // A fixed (`_`-only) gap after the first node pins that node's offset
+    // relative to its predecessor, so the pair has to be resolved as one
+    // rigid, anchor-and-chain block rather than intersected independently.
+    if plan
+        .segments
+        .iter()
+        .skip(1)
+        .any(|seg| matches!(seg.segment.gap_before, Gap::Fixed(_)))
+    {
+        let segments: Vec<GappedSegment> = plan.segments.iter().map(|s| s.segment).collect();
+        return (
+            count_rows_with_gapped_pattern(fm_database, table_name, pattern, &segments),
+            None,
+        );
+    }
 
-    let mut row_sets = Vec::new();
-    for lit in literals.iter() {
+    // Cost-based plan: `backward_search` already hands back a suffix-array
+    // range whose length is the literal's exact occurrence count, so rank
+    // segments by that instead of the planner's static length estimate. A
+    // literal absent from the corpus makes the whole pattern unmatchable.
+    let mut ranges: Vec<(&str, usize)> = Vec::with_capacity(plan.segments.len());
+    for seg in &plan.segments {
+        match fm_database.fm.backward_search(seg.segment.literal.as_bytes()) {
+            Some(range) => ranges.push((seg.segment.literal, range.1 - range.0)),
+            None => return (0, None),
+        }
+    }
+    ranges.sort_by_key(|&(_, range_len)| range_len);
+
+    let selectivity = ranges[0].1;
+    if selectivity > fm_database.max_range {
+        return (
+            count_like_match_all(fm_database, table_name, pattern),
+            None,
+        );
+    }
+
+    let plan = FmPlan {
+        literal_order: ranges.iter().map(|&(lit, _)| lit.to_string()).collect(),
+        selectivity,
+    };
+
+    let mut row_sets = Vec::with_capacity(ranges.len());
+    for &(lit, _) in &ranges {
         if let Some(set) = rows_for_literal(fm_database, lit, fm_literal_cache) {
             if set.is_empty() {
-                return 0;
+                return (0, Some(plan));
             }
             row_sets.push(set);
         } else {
-            return count_like_match_all(fm_database, table_name, pattern);
+            return (
+                count_like_match_all(fm_database, table_name, pattern),
+                Some(plan),
+            );
         }
     }
 
     let candidate_rows = intersect_row_sets(&mut row_sets);
     if candidate_rows.is_empty() {
-        return 0;
+        return (0, Some(plan));
     }
 
     let mut matched = 0usize;
@@ -539,7 +1089,7 @@ fn fm_like_search_table<'a>(
         }
     }
 
-    matched
+    (matched, Some(plan))
 }
 
 fn trigram_like_search_table<'a>(
@@ -548,11 +1098,13 @@ fn trigram_like_search_table<'a>(
     pattern: &Pattern<'a, StdSearch>,
     pattern_str: &str,
 ) -> usize {
-    let literals = split_literals(pattern_str);
-    let literal = literals
-        .into_iter()
-        .filter(|lit| lit.len() >= 3)
-        .max_by_key(|lit| lit.len());
+    let plan = build_like_plan(pattern_str);
+    let literal = plan
+        .segments
+        .iter()
+        .filter(|seg| seg.probe == ProbeIndex::Trigram)
+        .max_by_key(|seg| seg.segment.literal.len())
+        .map(|seg| seg.segment.literal);
 
     if let Some(lit) = literal {
         if let Some(candidate_ids) = trigram_database.index.search_literal(lit) {
@@ -612,29 +1164,266 @@ fn simple_like_kind(pattern: &str) -> SimpleLike<'_> {
     }
 }
 
-fn split_literals(pattern: &str) -> Vec<&str> {
-    let mut literals = Vec::new();
-    let mut start = None;
+/// Which index is worth probing for a [`LikeSegment`]'s literal. The trigram
+/// index only carries postings for 3+-byte trigrams, so a shorter segment is
+/// tagged `None` here; the FM index has no such floor and can
+/// `backward_search` a segment of any length, so it consults `segments`
+/// directly rather than filtering on this tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProbeIndex {
+    Trigram,
+    None,
+}
+
+/// One literal node of a [`LikePlan`]: the segment itself -- literal text
+/// plus the gap edge tying it to the segment before it -- and which index is
+/// worth probing for it.
+#[derive(Debug, Clone, Copy)]
+struct LikeSegment<'a> {
+    segment: GappedSegment<'a>,
+    probe: ProbeIndex,
+}
+
+/// A `LIKE` pattern's query plan: built once and shared by every index-backed
+/// algorithm instead of each backend re-deriving its own heuristic from the
+/// raw pattern string. `simple` captures the closed-form all/exact/contains/
+/// prefix/suffix shapes a backend can resolve without consulting `segments`
+/// at all. `segments` holds every literal node in source order with its gap
+/// edge intact -- the adjacency/ordering constraint a chained verify walks --
+/// tagged with which index type is worth probing for it. `full_scan` is set
+/// when the pattern carries no literal whatsoever, so no index probe can
+/// possibly narrow the candidate set and the executor should fall back to a
+/// linear `like_match` pass.
+struct LikePlan<'a> {
+    simple: SimpleLike<'a>,
+    segments: Vec<LikeSegment<'a>>,
+    full_scan: bool,
+}
+
+/// Build the shared query plan for `pattern`. Both `fm_like_search_table` and
+/// `trigram_like_search_table` consume this instead of separately calling
+/// `simple_like_kind` and re-tokenizing the pattern into literals: the FM
+/// backend matches on `plan.simple` for its closed-form shortcuts and
+/// otherwise probes `plan.segments` via `backward_search`/`literal_positions`;
+/// the trigram backend filters `plan.segments` down to the ones tagged
+/// `ProbeIndex::Trigram` and probes the longest via `index.search_literal`.
+fn build_like_plan(pattern: &str) -> LikePlan<'_> {
+    let simple = simple_like_kind(pattern);
+    let raw_segments = segment_literals(pattern);
+    let full_scan = raw_segments.is_empty();
+
+    let segments = raw_segments
+        .into_iter()
+        .map(|segment| LikeSegment {
+            segment,
+            probe: if segment.literal.len() >= 3 {
+                ProbeIndex::Trigram
+            } else {
+                ProbeIndex::None
+            },
+        })
+        .collect();
+
+    LikePlan {
+        simple,
+        segments,
+        full_scan,
+    }
+}
+
+/// The gap separating a segment from the one before it: a run of `_` with no
+/// intervening `%` has a known fixed width, while any gap involving `%` is
+/// unbounded.
+#[derive(Debug, Clone, Copy)]
+enum Gap {
+    Fixed(usize),
+    Loose,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct GappedSegment<'a> {
+    gap_before: Gap,
+    literal: &'a str,
+}
+
+/// Split a pattern into literal segments, each annotated with the gap that
+/// precedes it. The gap width surviving the split is what lets
+/// [`count_rows_with_gapped_pattern`] verify a `_`-joined segment by exact
+/// offset instead of a full-row rescan.
+fn segment_literals(pattern: &str) -> Vec<GappedSegment<'_>> {
+    let mut segments = Vec::new();
+    let mut literal_start: Option<usize> = None;
+    let mut fixed_run = 0usize;
+    let mut loose = false;
 
     for (idx, ch) in pattern.char_indices() {
-        if ch == '%' || ch == '_' {
-            if let Some(s) = start.take() {
-                if s < idx {
-                    literals.push(&pattern[s..idx]);
+        if ch == '_' || ch == '%' {
+            if let Some(s) = literal_start.take() {
+                segments.push(GappedSegment {
+                    gap_before: if loose {
+                        Gap::Loose
+                    } else {
+                        Gap::Fixed(fixed_run)
+                    },
+                    literal: &pattern[s..idx],
+                });
+                fixed_run = 0;
+                loose = false;
+            }
+            if ch == '_' {
+                fixed_run += 1;
+            } else {
+                loose = true;
+            }
+        } else if literal_start.is_none() {
+            literal_start = Some(idx);
+        }
+    }
+
+    if let Some(s) = literal_start {
+        segments.push(GappedSegment {
+            gap_before: if loose {
+                Gap::Loose
+            } else {
+                Gap::Fixed(fixed_run)
+            },
+            literal: &pattern[s..],
+        });
+    }
+
+    segments
+}
+
+/// Verify the segments after the anchor occur in order: a `Gap::Fixed(g)`
+/// segment must start exactly `g` bytes after the previous one ends, while a
+/// `Gap::Loose` segment may start anywhere at or after the previous end and
+/// is located with a plain substring search over only the unconsumed
+/// remainder of the row.
+///
+/// A `Gap::Loose` segment backtracks over every occurrence of its literal
+/// rather than committing to the first one `find` returns: the first
+/// occurrence may leave too little room for the segments still to come even
+/// though a later occurrence would satisfy them (e.g. chaining `Loose "X"`
+/// then `Fixed(0) "YZ"` over `"...X...XYZ..."` must skip the first `X` to
+/// reach the one `XYZ` actually confirms).
+fn chain_segments_forward(data: &str, cursor: usize, rest: &[GappedSegment<'_>]) -> bool {
+    let (seg, remaining) = match rest.split_first() {
+        Some(parts) => parts,
+        None => return true,
+    };
+    match seg.gap_before {
+        Gap::Fixed(g) => {
+            let start = cursor + g;
+            let end = start + seg.literal.len();
+            if end > data.len() || !data.is_char_boundary(start) {
+                return false;
+            }
+            if &data[start..end] != seg.literal {
+                return false;
+            }
+            chain_segments_forward(data, end, remaining)
+        }
+        Gap::Loose => {
+            if cursor > data.len() || !data.is_char_boundary(cursor) {
+                return false;
+            }
+            let mut search_from = cursor;
+            while let Some(rel) = data[search_from..].find(seg.literal) {
+                let occurrence_start = search_from + rel;
+                let next_cursor = occurrence_start + seg.literal.len();
+                if chain_segments_forward(data, next_cursor, remaining) {
+                    return true;
                 }
+                search_from = match data[occurrence_start..].char_indices().nth(1) {
+                    Some((offset, _)) => occurrence_start + offset,
+                    None => return false,
+                };
             }
-        } else if start.is_none() {
-            start = Some(idx);
+            false
         }
     }
+}
 
-    if let Some(s) = start {
-        if s < pattern.len() {
-            literals.push(&pattern[s..]);
+/// Count rows matching a pattern whose [`LikePlan`] segments include at least
+/// one fixed (`_`-only) gap, without rescanning every row. The rarest segment
+/// (by suffix-array range length, same metric as the FM backend's cost-based
+/// literal ordering) is located via [`literal_positions`] and each occurrence
+/// is mapped straight to its row with `row_index_for_pos`; the remaining
+/// segments are then confirmed in that one row by chaining offsets forward
+/// from the anchor instead of re-running a literal search over the whole
+/// table. A final `like_match` on the surviving candidate rows confirms the
+/// segments before the anchor and the pattern's leading and trailing anchors.
+/// This turns the common case into O(rarest-segment occurrences) work instead
+/// of O(total bytes).
+fn count_rows_with_gapped_pattern(
+    fm_database: &FmIndexDatabase<'_>,
+    table_name: &str,
+    pattern: &Pattern<'_, StdSearch>,
+    segments: &[GappedSegment<'_>],
+) -> usize {
+    if segments.is_empty() {
+        return count_like_match_all(fm_database, table_name, pattern);
+    }
+
+    let mut anchor_idx = 0;
+    let mut anchor_range = usize::MAX;
+    for (i, seg) in segments.iter().enumerate() {
+        match fm_database.fm.backward_search(seg.literal.as_bytes()) {
+            Some(range) => {
+                let range_len = range.1 - range.0;
+                if range_len < anchor_range {
+                    anchor_range = range_len;
+                    anchor_idx = i;
+                }
+            }
+            None => return 0,
         }
     }
 
-    literals
+    if anchor_range > fm_database.max_range {
+        return count_like_match_all(fm_database, table_name, pattern);
+    }
+
+    let anchor = segments[anchor_idx];
+    let anchor_positions = match literal_positions(fm_database, anchor.literal) {
+        Some(positions) => positions,
+        None => return count_like_match_all(fm_database, table_name, pattern),
+    };
+
+    let mut matched = vec![false; fm_database.rows.len()];
+    let mut count = 0usize;
+    for anchor_pos in anchor_positions {
+        let row_idx = match fm_database.row_index_for_pos(anchor_pos) {
+            Some(idx) => idx,
+            None => continue,
+        };
+        if matched[row_idx] {
+            continue;
+        }
+        let row = &fm_database.rows[row_idx];
+        if row.table != table_name {
+            continue;
+        }
+
+        let local_start = anchor_pos - row.start;
+        if local_start + anchor.literal.len() > row.data.len()
+            || !row.data[local_start..].starts_with(anchor.literal)
+        {
+            continue;
+        }
+
+        let after_anchor = local_start + anchor.literal.len();
+        if !chain_segments_forward(row.data, after_anchor, &segments[anchor_idx + 1..]) {
+            continue;
+        }
+
+        if like_match(pattern, row.data) {
+            matched[row_idx] = true;
+            count += 1;
+        }
+    }
+
+    count
 }
 
 fn count_all_rows(fm_database: &FmIndexDatabase<'_>, table_name: &str) -> usize {
@@ -657,12 +1446,12 @@ fn count_rows_with_literal(
     fm_database: &FmIndexDatabase<'_>,
     table_name: &str,
     lit: &str,
-    fm_literal_cache: &mut HashMap<String, HashSet<usize>>,
+    fm_literal_cache: &mut HashMap<String, RoaringBitmap>,
 ) -> usize {
     if let Some(rows) = rows_for_literal(fm_database, lit, fm_literal_cache) {
         return rows
-            .into_iter()
-            .filter(|&row_idx| fm_database.rows[row_idx].table == table_name)
+            .iter()
+            .filter(|&row_idx| fm_database.rows[row_idx as usize].table == table_name)
             .count();
     }
 
@@ -670,7 +1459,7 @@ fn count_rows_with_literal(
     s.push('%');
     s.push_str(lit);
     s.push('%');
-    let pattern = compile_pattern::<StdSearch, _, _>(&s, (), |_, pat| pat);
+    let pattern = compile_pattern::<StdSearch>(&s);
     count_like_match_all(fm_database, table_name, &pattern)
 }
 
@@ -685,7 +1474,7 @@ fn count_rows_with_prefix(fm_database: &FmIndexDatabase<'_>, table_name: &str, l
             let mut s = String::with_capacity(lit.len() + 1);
             s.push_str(lit);
             s.push('%');
-            let pattern = compile_pattern::<StdSearch, _, _>(&s, (), |_, pat| pat);
+            let pattern = compile_pattern::<StdSearch>(&s);
             return count_like_match_all(fm_database, table_name, &pattern);
         }
     };
@@ -719,7 +1508,7 @@ fn count_rows_with_suffix(fm_database: &FmIndexDatabase<'_>, table_name: &str, l
             let mut s = String::with_capacity(lit.len() + 1);
             s.push('%');
             s.push_str(lit);
-            let pattern = compile_pattern::<StdSearch, _, _>(&s, (), |_, pat| pat);
+            let pattern = compile_pattern::<StdSearch>(&s);
             return count_like_match_all(fm_database, table_name, &pattern);
         }
     };
@@ -757,8 +1546,8 @@ fn count_like_match_all(
 fn rows_for_literal(
     fm_database: &FmIndexDatabase<'_>,
     lit: &str,
-    fm_literal_cache: &mut HashMap<String, HashSet<usize>>,
-) -> Option<HashSet<usize>> {
+    fm_literal_cache: &mut HashMap<String, RoaringBitmap>,
+) -> Option<RoaringBitmap> {
     if let Some(cached) = fm_literal_cache.get(lit) {
         return Some(cached.clone());
     }
@@ -769,13 +1558,13 @@ fn rows_for_literal(
         return None;
     }
 
-    let mut rows = HashSet::new();
+    let mut rows = RoaringBitmap::new();
     let positions = fm_database.fm.search(lit.as_bytes());
     for pos in positions {
         if let Some(row_idx) = fm_database.row_index_for_pos(pos) {
             let row = &fm_database.rows[row_idx];
             if pos + lit.len() <= row.end {
-                rows.insert(row_idx);
+                rows.insert(row_idx as u32);
             }
         }
     }
@@ -794,15 +1583,11 @@ fn literal_positions(fm_database: &FmIndexDatabase<'_>, lit: &str) -> Option<Vec
     Some(fm_database.fm.search(lit.as_bytes()))
 }
 
-fn literal_rarity(lit: &str, byte_freq: &[usize; 256]) -> usize {
-    lit.as_bytes()
-        .iter()
-        .map(|&b| byte_freq[b as usize])
-        .min()
-        .unwrap_or(usize::MAX)
-}
-
-fn intersect_row_sets(sets: &mut Vec<HashSet<usize>>) -> Vec<usize> {
+/// AND a batch of per-literal row bitmaps together. Sorting by cardinality
+/// first means the smallest bitmap drives every subsequent `&=`, so an
+/// intersection with a rare literal stays cheap regardless of how many common
+/// literals follow it; an empty intermediate result short-circuits the rest.
+fn intersect_row_sets(sets: &mut Vec<RoaringBitmap>) -> Vec<usize> {
     if sets.is_empty() {
         return Vec::new();
     }
@@ -811,13 +1596,13 @@ fn intersect_row_sets(sets: &mut Vec<HashSet<usize>>) -> Vec<usize> {
     let mut iter = sets.iter();
     let mut acc = iter.next().cloned().unwrap_or_default();
     for set in iter {
-        acc.retain(|idx| set.contains(idx));
         if acc.is_empty() {
             break;
         }
+        acc &= set;
     }
 
-    acc.into_iter().collect()
+    acc.iter().map(|idx| idx as usize).collect()
 }
 
 fn skipped_entries<'a>(
@@ -835,9 +1620,10 @@ fn skipped_entries<'a>(
             pattern: pat_str.to_string(),
             file: table.name.clone(),
             file_type: infer_file_type(&table.name),
-            duration: Duration::from_micros(0),
+            timing: TimingStats::zero(),
             found_count: 0,
             skipped: true,
+            fm_plan: None,
         })
         .collect()
 }
@@ -847,7 +1633,6 @@ fn build_fm_index<'a>(database: &'a DataSet<'a>) -> (FmIndexDatabase<'a>, Durati
     let mut text = Vec::new();
     let mut rows = Vec::new();
     let mut row_starts = Vec::new();
-    let mut byte_freq = [0usize; 256];
 
     for table in database.tables.iter() {
         let table_name = table.name.as_str();
@@ -861,10 +1646,6 @@ fn build_fm_index<'a>(database: &'a DataSet<'a>) -> (FmIndexDatabase<'a>, Durati
             text.extend_from_slice(bytes);
             let end_offset = text.len();
 
-            for &b in bytes {
-                byte_freq[b as usize] += 1;
-            }
-
             rows.push(FmRow {
                 table: table_name,
                 data: row.data,
@@ -888,7 +1669,6 @@ fn build_fm_index<'a>(database: &'a DataSet<'a>) -> (FmIndexDatabase<'a>, Durati
             fm,
             rows,
             row_starts,
-            byte_freq,
             max_range,
         },
         duration,
@@ -955,16 +1735,14 @@ fn infer_file_type(file_name: &str) -> String {
 }
 
 fn print_summary_table(results: &[ResultEntry]) {
-    println!("\n\n{:=^95}", " RESULTS SUMMARY ");
+    println!("\n\n{:=^118}", " RESULTS SUMMARY ");
     println!(
-        "{:<15} | {:<20} | {:<20} | {:<6} | {:>10} | {:>15}",
-        "Algorithm", "Pattern", "File", "Type", "Hits", "Time (µs)"
+        "{:<15} | {:<20} | {:<20} | {:<6} | {:>10} | {:>17} | {:>10} | {:>4}",
+        "Algorithm", "Pattern", "File", "Type", "Hits", "Med (µs) [95% CI]", "p95 (µs)", "±"
     );
-    println!("{:-^95}", "");
+    println!("{:-^140}", "");
 
     for entry in results.iter().filter(|entry| !entry.skipped) {
-        let micros = entry.duration.as_micros() as f64;
-
         let pat_display = if entry.pattern.len() > 20 {
             format!("{}...", &entry.pattern[..17])
         } else {
@@ -977,41 +1755,88 @@ fn print_summary_table(results: &[ResultEntry]) {
             entry.file.clone()
         };
 
-        let hits_display = entry.found_count.to_string();
-        let time_display = format!("{:.2}", micros);
+        // A `~` marks cells whose relative standard error is too high to trust.
+        let noise_flag = if entry.timing.noisy { "~" } else { "" };
+        let median_with_ci = format!(
+            "{:.2} [{:.2},{:.2}]",
+            entry.timing.median_us(),
+            entry.timing.median_ci_low_ns / 1000.0,
+            entry.timing.median_ci_high_ns / 1000.0,
+        );
 
         println!(
-            "{:<15} | {:<20} | {:<20} | {:<6} | {:>10} | {:>15}",
-            entry.algo, pat_display, file_display, entry.file_type, hits_display, time_display
+            "{:<15} | {:<20} | {:<20} | {:<6} | {:>10} | {:>17} | {:>10.2} | {:>4}",
+            entry.algo,
+            pat_display,
+            file_display,
+            entry.file_type,
+            entry.found_count,
+            median_with_ci,
+            entry.timing.p95_ns / 1000.0,
+            noise_flag,
         );
     }
-    println!("{:=^95}", " END ");
+    println!("{:=^140}", " END ");
+}
+
+/// Summed median time and noisy-cell count for one algorithm across a group of
+/// cells. Ranking on the median total rather than a single wall-clock sum keeps
+/// the ordering reproducible; `noisy` counts how many cells were too imprecise
+/// to trust, so a regression can be told apart from measurement jitter.
+#[derive(Default, Clone, Copy)]
+struct RankAccum {
+    median_ns: f64,
+    ci_low_ns: f64,
+    ci_high_ns: f64,
+    noisy: usize,
+}
+
+impl RankAccum {
+    fn add(&mut self, timing: &TimingStats) {
+        self.median_ns += timing.median_ns;
+        self.ci_low_ns += timing.median_ci_low_ns;
+        self.ci_high_ns += timing.median_ci_high_ns;
+        if timing.noisy {
+            self.noisy += 1;
+        }
+    }
 }
 
 fn print_algo_ranking(results: &[ResultEntry]) {
-    println!("\n\n{:=^50}", " SPEED RANKING ");
+    println!("\n\n{:=^70}", " SPEED RANKING ");
     println!(
-        "{:<5} | {:<15} | {:>20}",
-        "Rank", "Algorithm", "Total Time (ms)"
+        "{:<5} | {:<15} | {:>15} | {:>20} | {:>8}",
+        "Rank", "Algorithm", "Median (ms)", "95% CI (ms)", "Noisy"
     );
-    println!("{:-^50}", "");
+    println!("{:-^70}", "");
 
-    let mut sums: HashMap<String, Duration> = HashMap::new();
+    let mut sums: HashMap<String, RankAccum> = HashMap::new();
 
     for entry in results.iter().filter(|entry| !entry.skipped) {
-        *sums.entry(entry.algo.clone()).or_default() += entry.duration;
+        sums.entry(entry.algo.clone()).or_default().add(&entry.timing);
     }
 
-    let mut ranked: Vec<(String, Duration)> = sums.into_iter().collect();
+    let mut ranked: Vec<(String, RankAccum)> = sums.into_iter().collect();
 
-    ranked.sort_by_key(|(_, duration)| *duration);
+    ranked.sort_by(|(_, a), (_, b)| a.median_ns.partial_cmp(&b.median_ns).unwrap());
 
-    for (i, (algo, duration)) in ranked.iter().enumerate() {
-        let millis = duration.as_millis();
-        println!("{:<5} | {:<15} | {:>20}", i + 1, algo, millis);
+    for (i, (algo, acc)) in ranked.iter().enumerate() {
+        let ci = format!(
+            "[{:.3},{:.3}]",
+            acc.ci_low_ns / 1_000_000.0,
+            acc.ci_high_ns / 1_000_000.0,
+        );
+        println!(
+            "{:<5} | {:<15} | {:>15.3} | {:>20} | {:>8}",
+            i + 1,
+            algo,
+            acc.median_ns / 1_000_000.0,
+            ci,
+            acc.noisy,
+        );
     }
 
-    println!("{:=^50}", " END ");
+    println!("{:=^70}", " END ");
 }
 
 fn print_per_pattern_ranking(results: &[ResultEntry]) {
@@ -1024,21 +1849,29 @@ fn print_per_pattern_ranking(results: &[ResultEntry]) {
     for pat in unique_patterns {
         println!("\n>> Pattern: [{}]", pat);
         println!(
-            "{:<5} | {:<15} | {:>20}",
-            "Rank", "Algorithm", "Total Time (µs)"
+            "{:<5} | {:<15} | {:>15} | {:>20} | {:>8}",
+            "Rank", "Algorithm", "Median (µs)", "95% CI (µs)", "Noisy"
         );
-        println!("{:-^46}", "");
+        println!("{:-^72}", "");
 
-        let mut sums: HashMap<&String, Duration> = HashMap::new();
+        let mut sums: HashMap<&String, RankAccum> = HashMap::new();
         for entry in results.iter().filter(|r| &r.pattern == pat && !r.skipped) {
-            *sums.entry(&entry.algo).or_default() += entry.duration;
+            sums.entry(&entry.algo).or_default().add(&entry.timing);
         }
 
-        let mut ranked: Vec<(&String, Duration)> = sums.into_iter().collect();
-        ranked.sort_by_key(|(_, d)| *d);
+        let mut ranked: Vec<(&String, RankAccum)> = sums.into_iter().collect();
+        ranked.sort_by(|(_, a), (_, b)| a.median_ns.partial_cmp(&b.median_ns).unwrap());
 
-        for (i, (algo, duration)) in ranked.iter().enumerate() {
-            println!("{:<5} | {:<15} | {:>20}", i + 1, algo, duration.as_micros());
+        for (i, (algo, acc)) in ranked.iter().enumerate() {
+            let ci = format!("[{:.2},{:.2}]", acc.ci_low_ns / 1000.0, acc.ci_high_ns / 1000.0);
+            println!(
+                "{:<5} | {:<15} | {:>15.2} | {:>20} | {:>8}",
+                i + 1,
+                algo,
+                acc.median_ns / 1000.0,
+                ci,
+                acc.noisy,
+            );
         }
     }
     println!("\n{:=^60}", " END PATTERN RANKING ");
@@ -1054,21 +1887,29 @@ fn print_per_file_ranking(results: &[ResultEntry]) {
     for file in unique_files {
         println!("\n>> File: [{}]", file);
         println!(
-            "{:<5} | {:<15} | {:>20}",
-            "Rank", "Algorithm", "Total Time (µs)"
+            "{:<5} | {:<15} | {:>15} | {:>20} | {:>8}",
+            "Rank", "Algorithm", "Median (µs)", "95% CI (µs)", "Noisy"
         );
-        println!("{:-^46}", "");
+        println!("{:-^72}", "");
 
-        let mut sums: HashMap<&String, Duration> = HashMap::new();
+        let mut sums: HashMap<&String, RankAccum> = HashMap::new();
         for entry in results.iter().filter(|r| &r.file == file && !r.skipped) {
-            *sums.entry(&entry.algo).or_default() += entry.duration;
+            sums.entry(&entry.algo).or_default().add(&entry.timing);
         }
 
-        let mut ranked: Vec<(&String, Duration)> = sums.into_iter().collect();
-        ranked.sort_by_key(|(_, d)| *d);
+        let mut ranked: Vec<(&String, RankAccum)> = sums.into_iter().collect();
+        ranked.sort_by(|(_, a), (_, b)| a.median_ns.partial_cmp(&b.median_ns).unwrap());
 
-        for (i, (algo, duration)) in ranked.iter().enumerate() {
-            println!("{:<5} | {:<15} | {:>20}", i + 1, algo, duration.as_micros());
+        for (i, (algo, acc)) in ranked.iter().enumerate() {
+            let ci = format!("[{:.2},{:.2}]", acc.ci_low_ns / 1000.0, acc.ci_high_ns / 1000.0);
+            println!(
+                "{:<5} | {:<15} | {:>15.2} | {:>20} | {:>8}",
+                i + 1,
+                algo,
+                acc.median_ns / 1000.0,
+                ci,
+                acc.noisy,
+            );
         }
     }
     println!("\n{:=^60}", " END FILE RANKING ");
@@ -1147,3 +1988,224 @@ fn print_correctness_report(results: &[ResultEntry]) {
 
     println!("{:=^70}", " END ");
 }
+
+/// Minimal JSON value used to read back a saved baseline. The tree has no serde
+/// dependency, so this is a small recursive-descent parser covering exactly the
+/// subset `results_to_json` emits: objects, arrays, strings, numbers, booleans,
+/// and null.
+enum JsonValue {
+    Null,
+    Bool(bool),
+    Num(f64),
+    Str(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    fn parse(text: &str) -> Result<JsonValue, String> {
+        let bytes = text.as_bytes();
+        let mut pos = 0;
+        let value = parse_value(bytes, &mut pos)?;
+        skip_ws(bytes, &mut pos);
+        if pos != bytes.len() {
+            return Err(format!("trailing characters at byte {}", pos));
+        }
+        Ok(value)
+    }
+
+    /// Look up a key on an object value.
+    fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Num(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    fn as_usize(&self) -> Option<usize> {
+        match self {
+            JsonValue::Num(n) => Some(*n as usize),
+            _ => None,
+        }
+    }
+}
+
+fn skip_ws(bytes: &[u8], pos: &mut usize) {
+    while *pos < bytes.len() && matches!(bytes[*pos], b' ' | b'\t' | b'\n' | b'\r') {
+        *pos += 1;
+    }
+}
+
+fn parse_value(bytes: &[u8], pos: &mut usize) -> Result<JsonValue, String> {
+    skip_ws(bytes, pos);
+    match bytes.get(*pos) {
+        Some(b'{') => parse_object(bytes, pos),
+        Some(b'[') => parse_array(bytes, pos),
+        Some(b'"') => parse_string(bytes, pos).map(JsonValue::Str),
+        Some(b't') => parse_literal(bytes, pos, "true", JsonValue::Bool(true)),
+        Some(b'f') => parse_literal(bytes, pos, "false", JsonValue::Bool(false)),
+        Some(b'n') => parse_literal(bytes, pos, "null", JsonValue::Null),
+        Some(_) => parse_number(bytes, pos),
+        None => Err("unexpected end of input".to_string()),
+    }
+}
+
+fn parse_literal(
+    bytes: &[u8],
+    pos: &mut usize,
+    word: &str,
+    value: JsonValue,
+) -> Result<JsonValue, String> {
+    if bytes[*pos..].starts_with(word.as_bytes()) {
+        *pos += word.len();
+        Ok(value)
+    } else {
+        Err(format!("expected `{}` at byte {}", word, *pos))
+    }
+}
+
+fn parse_object(bytes: &[u8], pos: &mut usize) -> Result<JsonValue, String> {
+    *pos += 1; // consume '{'
+    let mut fields = Vec::new();
+    skip_ws(bytes, pos);
+    if bytes.get(*pos) == Some(&b'}') {
+        *pos += 1;
+        return Ok(JsonValue::Object(fields));
+    }
+    loop {
+        skip_ws(bytes, pos);
+        let key = parse_string(bytes, pos)?;
+        skip_ws(bytes, pos);
+        if bytes.get(*pos) != Some(&b':') {
+            return Err(format!("expected `:` at byte {}", *pos));
+        }
+        *pos += 1;
+        let value = parse_value(bytes, pos)?;
+        fields.push((key, value));
+        skip_ws(bytes, pos);
+        match bytes.get(*pos) {
+            Some(b',') => *pos += 1,
+            Some(b'}') => {
+                *pos += 1;
+                break;
+            }
+            _ => return Err(format!("expected `,` or `}}` at byte {}", *pos)),
+        }
+    }
+    Ok(JsonValue::Object(fields))
+}
+
+fn parse_array(bytes: &[u8], pos: &mut usize) -> Result<JsonValue, String> {
+    *pos += 1; // consume '['
+    let mut items = Vec::new();
+    skip_ws(bytes, pos);
+    if bytes.get(*pos) == Some(&b']') {
+        *pos += 1;
+        return Ok(JsonValue::Array(items));
+    }
+    loop {
+        let value = parse_value(bytes, pos)?;
+        items.push(value);
+        skip_ws(bytes, pos);
+        match bytes.get(*pos) {
+            Some(b',') => *pos += 1,
+            Some(b']') => {
+                *pos += 1;
+                break;
+            }
+            _ => return Err(format!("expected `,` or `]` at byte {}", *pos)),
+        }
+    }
+    Ok(JsonValue::Array(items))
+}
+
+fn parse_string(bytes: &[u8], pos: &mut usize) -> Result<String, String> {
+    if bytes.get(*pos) != Some(&b'"') {
+        return Err(format!("expected string at byte {}", *pos));
+    }
+    *pos += 1;
+    let mut out = String::new();
+    while let Some(&b) = bytes.get(*pos) {
+        match b {
+            b'"' => {
+                *pos += 1;
+                return Ok(out);
+            }
+            b'\\' => {
+                *pos += 1;
+                match bytes.get(*pos) {
+                    Some(b'"') => out.push('"'),
+                    Some(b'\\') => out.push('\\'),
+                    Some(b'/') => out.push('/'),
+                    Some(b'n') => out.push('\n'),
+                    Some(b'r') => out.push('\r'),
+                    Some(b't') => out.push('\t'),
+                    Some(b'u') => {
+                        let hex = bytes
+                            .get(*pos + 1..*pos + 5)
+                            .ok_or_else(|| "truncated \\u escape".to_string())?;
+                        let code = u32::from_str_radix(
+                            std::str::from_utf8(hex).map_err(|_| "bad \\u escape".to_string())?,
+                            16,
+                        )
+                        .map_err(|_| "bad \\u escape".to_string())?;
+                        out.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                        *pos += 4;
+                    }
+                    _ => return Err(format!("bad escape at byte {}", *pos)),
+                }
+                *pos += 1;
+            }
+            _ => {
+                // Copy the full UTF-8 sequence of this character.
+                let start = *pos;
+                let len = utf8_len(b);
+                *pos += len;
+                out.push_str(
+                    std::str::from_utf8(&bytes[start..start + len])
+                        .map_err(|_| "invalid utf-8 in string".to_string())?,
+                );
+            }
+        }
+    }
+    Err("unterminated string".to_string())
+}
+
+fn utf8_len(lead: u8) -> usize {
+    match lead {
+        b if b < 0x80 => 1,
+        b if b >> 5 == 0b110 => 2,
+        b if b >> 4 == 0b1110 => 3,
+        _ => 4,
+    }
+}
+
+fn parse_number(bytes: &[u8], pos: &mut usize) -> Result<JsonValue, String> {
+    let start = *pos;
+    while let Some(&b) = bytes.get(*pos) {
+        if matches!(b, b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E') {
+            *pos += 1;
+        } else {
+            break;
+        }
+    }
+    let slice = std::str::from_utf8(&bytes[start..*pos]).map_err(|_| "bad number".to_string())?;
+    slice
+        .parse::<f64>()
+        .map(JsonValue::Num)
+        .map_err(|_| format!("bad number `{}`", slice))
+}
@@ -4,6 +4,7 @@ use std::time::Instant;
 
 use storage::BumpArena;
 use storage::fasta::parse_fasta_into_arena;
+use storage::interner::Interner;
 
 pub fn main() {
     let arena_size = 1024 * 1024 * 1024;
@@ -22,6 +23,7 @@ pub fn main() {
 
     let mut total_entries = 0;
     let mut total_bytes_read = 0;
+    let interner = Interner::new(&arena);
     let global_start = Instant::now();
 
     let paths = fs::read_dir(data_path).expect("Could not read data dir");
@@ -45,6 +47,10 @@ pub fn main() {
                     let mut seq_len_sum: usize = 0;
                     for entry in entries.iter() {
                         seq_len_sum += entry.data.len();
+                        // Fold record ids and descriptions into the atom table
+                        // so repeated tags collapse to a single arena copy.
+                        interner.intern(entry.id);
+                        interner.intern(entry.desc);
                     }
 
                     println!(
@@ -69,6 +75,12 @@ pub fn main() {
     println!("Total Files Loaded:  {} bytes", total_bytes_read);
     println!("Total Entries:       {}", total_entries);
     println!("Arena Memory Used:   {} MB", arena.used() / 1024 / 1024);
+    println!(
+        "Interned id/desc:    {} unique / {} total ({:.1}% deduped)",
+        interner.len(),
+        interner.interned_count(),
+        interner.dedup_ratio() * 100.0
+    );
     println!("Total Time:          {:.2?}", total_duration);
 
     assert!(